@@ -0,0 +1,55 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+use tcolour::{BlendMode, Colour};
+
+const BLEND_MODES: [BlendMode; 19] = [
+    BlendMode::Normal,
+    BlendMode::Multiply,
+    BlendMode::Divide,
+    BlendMode::Addition,
+    BlendMode::Subtract,
+    BlendMode::Screen,
+    BlendMode::Overlay,
+    BlendMode::HardLight,
+    BlendMode::SoftLight,
+    BlendMode::Darken,
+    BlendMode::Lighten,
+    BlendMode::LinearBurn,
+    BlendMode::LinearLight,
+    BlendMode::VividLight,
+    BlendMode::PinLight,
+    BlendMode::Hue,
+    BlendMode::Saturation,
+    BlendMode::Color,
+    BlendMode::Luminosity,
+];
+
+fn layers() -> Vec<(Colour, Colour)> {
+    (0..256)
+        .map(|i| {
+            let v = i as f64 / 255f64;
+            (
+                Colour::new(v, 1f64 - v, (v * 7f64) % 1f64, 0.5f64 + v / 2f64),
+                Colour::new((v * 3f64) % 1f64, v, 1f64 - v, 1f64 - v / 2f64),
+            )
+        })
+        .collect()
+}
+
+fn blend_benchmark(c: &mut Criterion) {
+    let layers = layers();
+    let mut group = c.benchmark_group("blend");
+    for blend_mode in BLEND_MODES {
+        group.bench_function(format!("{blend_mode:?}"), |b| {
+            b.iter(|| {
+                for &(base, blend) in &layers {
+                    black_box(black_box(base).blend(black_box(blend), blend_mode));
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, blend_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,36 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+use tcolour::{Colour, Gradient};
+
+fn strip() -> (Gradient, Vec<f64>) {
+    let gradient = Gradient(
+        (0..16)
+            .map(|i| {
+                let v = i as f64 / 15f64;
+                (v, Colour::solid(v, (v * 3f64) % 1f64, 1f64 - v))
+            })
+            .collect(),
+    );
+    let ts = (0..4096).map(|i| i as f64 / 4095f64).collect();
+    (gradient, ts)
+}
+
+fn sample_benchmark(c: &mut Criterion) {
+    let (gradient, ts) = strip();
+    let mut group = c.benchmark_group("gradient_strip_4096");
+    group.bench_function("sample", |b| {
+        b.iter(|| {
+            black_box(&ts)
+                .iter()
+                .map(|&t| gradient.sample(t))
+                .collect::<Vec<Colour>>()
+        })
+    });
+    group.bench_function("sample_many", |b| {
+        b.iter(|| gradient.sample_many(black_box(&ts)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, sample_benchmark);
+criterion_main!(benches);
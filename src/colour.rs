@@ -1,6 +1,43 @@
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+#[cfg(feature = "approx")]
 use approx::{AbsDiffEq, RelativeEq, UlpsEq};
 use auto_ops::{impl_op_ex, impl_op_ex_commutative};
-use std::convert::TryFrom;
+#[cfg(feature = "alloc")]
+use core::convert::TryFrom;
+use core::fmt;
+
+#[cfg(feature = "std")]
+use crate::lut::Lut3d;
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use crate::math::Float;
+
+/// Errors produced by the fallible conversions of this crate
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ColourError {
+    /// A flat buffer's length was not a multiple of 4
+    InvalidBufferLength(usize),
+    /// A channel of a colour was `NaN` or infinite
+    NonFiniteChannel,
+}
+
+impl fmt::Display for ColourError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ColourError::InvalidBufferLength(len) => {
+                write!(f, "buffer length {len} is not a multiple of 4")
+            }
+            ColourError::NonFiniteChannel => write!(f, "colour has a NaN or infinite channel"),
+        }
+    }
+}
+
+impl core::error::Error for ColourError {}
 
 #[cfg_attr(test, derive(strum_macros::EnumIter))]
 #[derive(Clone, Copy, PartialEq, Debug)]
@@ -20,6 +57,129 @@ pub enum BlendMode {
 
     Darken,
     Lighten,
+
+    /// `base + blend - 1`
+    LinearBurn,
+    /// `base + 2 * blend - 1`
+    LinearLight,
+    /// Colour burns below `0.5` and colour dodges above it
+    VividLight,
+    /// Darkens below `0.5` and lightens above it
+    PinLight,
+
+    /// The hue of the blend layer with the saturation and luminosity
+    /// of the base layer
+    Hue,
+    /// The saturation of the blend layer with the hue and luminosity
+    /// of the base layer
+    Saturation,
+    /// The hue and saturation of the blend layer with the luminosity
+    /// of the base layer
+    Color,
+    /// The luminosity of the blend layer with the hue and saturation
+    /// of the base layer
+    Luminosity,
+}
+
+/// Writes the mode's name in kebab-case, like CSS's `mix-blend-mode`,
+/// which `str::parse()` reads back.
+///
+/// # Example
+/// ```
+/// use tcolour::BlendMode;
+///
+/// assert_eq!(BlendMode::HardLight.to_string(), "hard-light");
+/// assert_eq!("HardLight".parse::<BlendMode>(), Ok(BlendMode::HardLight));
+/// assert_eq!("hard_light".parse::<BlendMode>(), Ok(BlendMode::HardLight));
+/// ```
+impl fmt::Display for BlendMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            BlendMode::Normal => "normal",
+            BlendMode::Multiply => "multiply",
+            BlendMode::Divide => "divide",
+            BlendMode::Addition => "addition",
+            BlendMode::Subtract => "subtract",
+            BlendMode::Screen => "screen",
+            BlendMode::Overlay => "overlay",
+            BlendMode::HardLight => "hard-light",
+            BlendMode::SoftLight => "soft-light",
+            BlendMode::Darken => "darken",
+            BlendMode::Lighten => "lighten",
+            BlendMode::LinearBurn => "linear-burn",
+            BlendMode::LinearLight => "linear-light",
+            BlendMode::VividLight => "vivid-light",
+            BlendMode::PinLight => "pin-light",
+            BlendMode::Hue => "hue",
+            BlendMode::Saturation => "saturation",
+            BlendMode::Color => "color",
+            BlendMode::Luminosity => "luminosity",
+        })
+    }
+}
+
+/// Parses a mode's name ignoring case, surrounding whitespace and any
+/// `-`, `_` or spaces between words, so `"hard-light"`, `"hard_light"`
+/// and `"HardLight"` are all `BlendMode::HardLight`. `"colour"` is also
+/// accepted for `BlendMode::Color`.
+#[cfg(feature = "alloc")]
+impl core::str::FromStr for BlendMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name: String = s
+            .trim()
+            .chars()
+            .filter(|c| !matches!(c, '-' | '_' | ' '))
+            .collect();
+        Ok(match name.to_ascii_lowercase().as_str() {
+            "normal" => BlendMode::Normal,
+            "multiply" => BlendMode::Multiply,
+            "divide" => BlendMode::Divide,
+            "addition" => BlendMode::Addition,
+            "subtract" => BlendMode::Subtract,
+            "screen" => BlendMode::Screen,
+            "overlay" => BlendMode::Overlay,
+            "hardlight" => BlendMode::HardLight,
+            "softlight" => BlendMode::SoftLight,
+            "darken" => BlendMode::Darken,
+            "lighten" => BlendMode::Lighten,
+            "linearburn" => BlendMode::LinearBurn,
+            "linearlight" => BlendMode::LinearLight,
+            "vividlight" => BlendMode::VividLight,
+            "pinlight" => BlendMode::PinLight,
+            "hue" => BlendMode::Hue,
+            "saturation" => BlendMode::Saturation,
+            "color" | "colour" => BlendMode::Color,
+            "luminosity" => BlendMode::Luminosity,
+            _ => return Err(format!("`{s}` is not a blend mode.")),
+        })
+    }
+}
+
+/// The space `Colour::blend_in()` blends colours in
+#[cfg_attr(test, derive(strum_macros::EnumIter))]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum BlendSpace {
+    /// Blend the gamma-encoded channels directly, like `Colour::blend()`
+    Srgb,
+    /// Blend in linear light, like `Colour::blend_linear()`
+    Linear,
+}
+
+/// The space `Colour::mix()` interpolates colours in
+#[cfg_attr(test, derive(strum_macros::EnumIter))]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MixSpace {
+    /// Interpolate the gamma-encoded channels, like `Colour::lerp()`
+    Rgb,
+    /// Interpolate in linear light
+    LinearRgb,
+    /// Interpolate hue, saturation and lightness, taking the shorter
+    /// way around the hue circle
+    Hsl,
+    /// Interpolate in OKLab, perceptually even and good for UI tweens
+    Oklab,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -72,6 +232,52 @@ impl UlpsEq for Colour {
     }
 }
 
+/// Formats the colour as `rgba(r, g, b, a)`, using 3 decimal places
+/// unless a precision is given.
+///
+/// # Example
+/// ```
+/// use tcolour::Colour;
+///
+/// let colour = Colour::new(1.0, 0.5, 0.0, 1.0);
+///
+/// assert_eq!(format!("{colour}"), "rgba(1.000, 0.500, 0.000, 1.000)");
+/// assert_eq!(format!("{colour:.1}"), "rgba(1.0, 0.5, 0.0, 1.0)");
+/// ```
+impl fmt::Display for Colour {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let precision = f.precision().unwrap_or(3);
+        write!(
+            f,
+            "rgba({:.*}, {:.*}, {:.*}, {:.*})",
+            precision, self.r, precision, self.g, precision, self.b, precision, self.a
+        )
+    }
+}
+
+/// Indexes the channels `0..=3` as `r`, `g`, `b` and `a`, panicking
+/// on any other index like a slice. Use `.channel()` to get an `Option`.
+impl core::ops::Index<usize> for Colour {
+    type Output = f64;
+
+    fn index(&self, i: usize) -> &f64 {
+        match i {
+            0 => &self.r,
+            1 => &self.g,
+            2 => &self.b,
+            3 => &self.a,
+            _ => panic!("index out of bounds: the len is 4 but the index is {i}"),
+        }
+    }
+}
+
+impl core::ops::IndexMut<usize> for Colour {
+    fn index_mut(&mut self, i: usize) -> &mut f64 {
+        self.channel_mut(i)
+            .unwrap_or_else(|| panic!("index out of bounds: the len is 4 but the index is {i}"))
+    }
+}
+
 // ---------- Implemented operators for Colour ----------
 impl_op_ex_commutative!(+|a: &Colour, b: &f64| -> Colour {
     Colour::new(a.r + b, a.g + b, a.b + b, a.a)
@@ -104,43 +310,599 @@ impl_op_ex!(/|a: &Colour, b: &Colour| -> Colour {
 
 impl_op_ex!(-|a: &Colour| -> Colour { a.inverted() });
 
+/// The W3C colour dodge of a single channel
+fn colour_dodge(base: f64, blend: f64) -> f64 {
+    if base == 0f64 {
+        0f64
+    } else if blend >= 1f64 {
+        1f64
+    } else {
+        (base / (1f64 - blend)).min(1f64)
+    }
+}
+
+/// The W3C colour burn of a single channel
+fn colour_burn(base: f64, blend: f64) -> f64 {
+    if base >= 1f64 {
+        1f64
+    } else if blend == 0f64 {
+        0f64
+    } else {
+        1f64 - ((1f64 - base) / blend).min(1f64)
+    }
+}
+
+/// `.cleaned()` for a single channel
+fn clean_channel(v: f64) -> f64 {
+    if !(v.is_normal() || v == 0f64) {
+        1f64
+    } else {
+        v
+    }
+}
+
+/// The overlay of a single channel
+fn overlay(base: f64, blend: f64) -> f64 {
+    if base < 0.5f64 {
+        2f64 * blend * base
+    } else {
+        1f64 - 2f64 * (1f64 - base) * (1f64 - blend)
+    }
+}
+
+/// Blends each RGB channel of `base` and `blend` with `blend_channel`
+fn blend_channels<F: Fn(f64, f64) -> f64>(
+    base: &Colour,
+    blend: &Colour,
+    blend_channel: F,
+) -> [f64; 3] {
+    [
+        blend_channel(base.r, blend.r),
+        blend_channel(base.g, blend.g),
+        blend_channel(base.b, blend.b),
+    ]
+}
+
+/// The first 16 colours of the xterm-256 palette, these match the
+/// named ratatui colours
+const ANSI_COLOURS: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (0, 0, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (169, 169, 169),
+    (128, 128, 128),
+    (255, 128, 128),
+    (128, 255, 128),
+    (255, 255, 128),
+    (128, 128, 255),
+    (255, 128, 255),
+    (128, 255, 255),
+    (255, 255, 255),
+];
+
+/// The channel levels of the xterm-256 6x6x6 colour cube
+const XTERM_CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// The 148 CSS named colours, sorted by name for `Colour::from_name()`
+const CSS_NAMED_COLOURS: [(&str, (u8, u8, u8)); 148] = [
+    ("aliceblue", (240, 248, 255)),
+    ("antiquewhite", (250, 235, 215)),
+    ("aqua", (0, 255, 255)),
+    ("aquamarine", (127, 255, 212)),
+    ("azure", (240, 255, 255)),
+    ("beige", (245, 245, 220)),
+    ("bisque", (255, 228, 196)),
+    ("black", (0, 0, 0)),
+    ("blanchedalmond", (255, 235, 205)),
+    ("blue", (0, 0, 255)),
+    ("blueviolet", (138, 43, 226)),
+    ("brown", (165, 42, 42)),
+    ("burlywood", (222, 184, 135)),
+    ("cadetblue", (95, 158, 160)),
+    ("chartreuse", (127, 255, 0)),
+    ("chocolate", (210, 105, 30)),
+    ("coral", (255, 127, 80)),
+    ("cornflowerblue", (100, 149, 237)),
+    ("cornsilk", (255, 248, 220)),
+    ("crimson", (220, 20, 60)),
+    ("cyan", (0, 255, 255)),
+    ("darkblue", (0, 0, 139)),
+    ("darkcyan", (0, 139, 139)),
+    ("darkgoldenrod", (184, 134, 11)),
+    ("darkgray", (169, 169, 169)),
+    ("darkgreen", (0, 100, 0)),
+    ("darkgrey", (169, 169, 169)),
+    ("darkkhaki", (189, 183, 107)),
+    ("darkmagenta", (139, 0, 139)),
+    ("darkolivegreen", (85, 107, 47)),
+    ("darkorange", (255, 140, 0)),
+    ("darkorchid", (153, 50, 204)),
+    ("darkred", (139, 0, 0)),
+    ("darksalmon", (233, 150, 122)),
+    ("darkseagreen", (143, 188, 143)),
+    ("darkslateblue", (72, 61, 139)),
+    ("darkslategray", (47, 79, 79)),
+    ("darkslategrey", (47, 79, 79)),
+    ("darkturquoise", (0, 206, 209)),
+    ("darkviolet", (148, 0, 211)),
+    ("deeppink", (255, 20, 147)),
+    ("deepskyblue", (0, 191, 255)),
+    ("dimgray", (105, 105, 105)),
+    ("dimgrey", (105, 105, 105)),
+    ("dodgerblue", (30, 144, 255)),
+    ("firebrick", (178, 34, 34)),
+    ("floralwhite", (255, 250, 240)),
+    ("forestgreen", (34, 139, 34)),
+    ("fuchsia", (255, 0, 255)),
+    ("gainsboro", (220, 220, 220)),
+    ("ghostwhite", (248, 248, 255)),
+    ("gold", (255, 215, 0)),
+    ("goldenrod", (218, 165, 32)),
+    ("gray", (128, 128, 128)),
+    ("green", (0, 128, 0)),
+    ("greenyellow", (173, 255, 47)),
+    ("grey", (128, 128, 128)),
+    ("honeydew", (240, 255, 240)),
+    ("hotpink", (255, 105, 180)),
+    ("indianred", (205, 92, 92)),
+    ("indigo", (75, 0, 130)),
+    ("ivory", (255, 255, 240)),
+    ("khaki", (240, 230, 140)),
+    ("lavender", (230, 230, 250)),
+    ("lavenderblush", (255, 240, 245)),
+    ("lawngreen", (124, 252, 0)),
+    ("lemonchiffon", (255, 250, 205)),
+    ("lightblue", (173, 216, 230)),
+    ("lightcoral", (240, 128, 128)),
+    ("lightcyan", (224, 255, 255)),
+    ("lightgoldenrodyellow", (250, 250, 210)),
+    ("lightgray", (211, 211, 211)),
+    ("lightgreen", (144, 238, 144)),
+    ("lightgrey", (211, 211, 211)),
+    ("lightpink", (255, 182, 193)),
+    ("lightsalmon", (255, 160, 122)),
+    ("lightseagreen", (32, 178, 170)),
+    ("lightskyblue", (135, 206, 250)),
+    ("lightslategray", (119, 136, 153)),
+    ("lightslategrey", (119, 136, 153)),
+    ("lightsteelblue", (176, 196, 222)),
+    ("lightyellow", (255, 255, 224)),
+    ("lime", (0, 255, 0)),
+    ("limegreen", (50, 205, 50)),
+    ("linen", (250, 240, 230)),
+    ("magenta", (255, 0, 255)),
+    ("maroon", (128, 0, 0)),
+    ("mediumaquamarine", (102, 205, 170)),
+    ("mediumblue", (0, 0, 205)),
+    ("mediumorchid", (186, 85, 211)),
+    ("mediumpurple", (147, 112, 219)),
+    ("mediumseagreen", (60, 179, 113)),
+    ("mediumslateblue", (123, 104, 238)),
+    ("mediumspringgreen", (0, 250, 154)),
+    ("mediumturquoise", (72, 209, 204)),
+    ("mediumvioletred", (199, 21, 133)),
+    ("midnightblue", (25, 25, 112)),
+    ("mintcream", (245, 255, 250)),
+    ("mistyrose", (255, 228, 225)),
+    ("moccasin", (255, 228, 181)),
+    ("navajowhite", (255, 222, 173)),
+    ("navy", (0, 0, 128)),
+    ("oldlace", (253, 245, 230)),
+    ("olive", (128, 128, 0)),
+    ("olivedrab", (107, 142, 35)),
+    ("orange", (255, 165, 0)),
+    ("orangered", (255, 69, 0)),
+    ("orchid", (218, 112, 214)),
+    ("palegoldenrod", (238, 232, 170)),
+    ("palegreen", (152, 251, 152)),
+    ("paleturquoise", (175, 238, 238)),
+    ("palevioletred", (219, 112, 147)),
+    ("papayawhip", (255, 239, 213)),
+    ("peachpuff", (255, 218, 185)),
+    ("peru", (205, 133, 63)),
+    ("pink", (255, 192, 203)),
+    ("plum", (221, 160, 221)),
+    ("powderblue", (176, 224, 230)),
+    ("purple", (128, 0, 128)),
+    ("rebeccapurple", (102, 51, 153)),
+    ("red", (255, 0, 0)),
+    ("rosybrown", (188, 143, 143)),
+    ("royalblue", (65, 105, 225)),
+    ("saddlebrown", (139, 69, 19)),
+    ("salmon", (250, 128, 114)),
+    ("sandybrown", (244, 164, 96)),
+    ("seagreen", (46, 139, 87)),
+    ("seashell", (255, 245, 238)),
+    ("sienna", (160, 82, 45)),
+    ("silver", (192, 192, 192)),
+    ("skyblue", (135, 206, 235)),
+    ("slateblue", (106, 90, 205)),
+    ("slategray", (112, 128, 144)),
+    ("slategrey", (112, 128, 144)),
+    ("snow", (255, 250, 250)),
+    ("springgreen", (0, 255, 127)),
+    ("steelblue", (70, 130, 180)),
+    ("tan", (210, 180, 140)),
+    ("teal", (0, 128, 128)),
+    ("thistle", (216, 191, 216)),
+    ("tomato", (255, 99, 71)),
+    ("turquoise", (64, 224, 208)),
+    ("violet", (238, 130, 238)),
+    ("wheat", (245, 222, 179)),
+    ("white", (255, 255, 255)),
+    ("whitesmoke", (245, 245, 245)),
+    ("yellow", (255, 255, 0)),
+    ("yellowgreen", (154, 205, 50)),
+];
+
+/// The W3C `Lum` of the colour, used by the non-separable blend modes
+fn lum(colour: Colour) -> f64 {
+    0.3f64 * colour.r + 0.59f64 * colour.g + 0.11f64 * colour.b
+}
+
+/// The W3C `ClipColor`, brings the channels back between `[0, 1]`
+/// while holding the `Lum` of the colour.
+fn clip_color(colour: Colour) -> Colour {
+    let l = lum(colour);
+    let min = colour.r.min(colour.g.min(colour.b));
+    let max = colour.r.max(colour.g.max(colour.b));
+    let mut colour = colour;
+    if min < 0f64 {
+        colour = colour.map(|v| l + (v - l) * l / (l - min));
+    }
+    if max > 1f64 {
+        colour = colour.map(|v| l + (v - l) * (1f64 - l) / (max - l));
+    }
+    colour
+}
+
+/// The W3C `SetLum`, shifts the colour to have the given `Lum`
+fn set_lum(colour: Colour, l: f64) -> Colour {
+    clip_color(colour + (l - lum(colour)))
+}
+
+/// The W3C `Sat`, the range of the RGB channels
+fn sat(colour: Colour) -> f64 {
+    colour.r.max(colour.g.max(colour.b)) - colour.r.min(colour.g.min(colour.b))
+}
+
+/// The W3C `SetSat`, stretches the RGB channels to have the given `Sat`
+fn set_sat(colour: Colour, s: f64) -> Colour {
+    let mut channels = [colour.r, colour.g, colour.b];
+    let mut order = [0, 1, 2];
+    order.sort_unstable_by(|&i, &j| channels[i].total_cmp(&channels[j]).then(i.cmp(&j)));
+    let [min, mid, max] = order;
+    if channels[max] > channels[min] {
+        channels[mid] = (channels[mid] - channels[min]) * s / (channels[max] - channels[min]);
+        channels[max] = s;
+    } else {
+        channels[mid] = 0f64;
+        channels[max] = 0f64;
+    }
+    channels[min] = 0f64;
+    Colour::new(channels[0], channels[1], channels[2], colour.a)
+}
+
+/// Evaluates the CSS timing function `cubic-bezier(p1.0, p1.1, p2.0, p2.1)`
+/// at `t` by solving the curve's x for `t` and returning its y.
+fn cubic_bezier(t: f64, p1: (f64, f64), p2: (f64, f64)) -> f64 {
+    let bezier = |s: f64, a: f64, b: f64| {
+        3f64 * (1f64 - s).powi(2) * s * a + 3f64 * (1f64 - s) * s.powi(2) * b + s.powi(3)
+    };
+    let t = t.clamp(0f64, 1f64);
+
+    // Newton's method converges quickly for most curves...
+    let mut s = t;
+    for _ in 0..8 {
+        let error = bezier(s, p1.0, p2.0) - t;
+        if error.abs() < 1e-12f64 {
+            return bezier(s, p1.1, p2.1);
+        }
+        let slope = 3f64 * (1f64 - s).powi(2) * p1.0
+            + 6f64 * (1f64 - s) * s * (p2.0 - p1.0)
+            + 3f64 * s.powi(2) * (1f64 - p2.0);
+        if slope.abs() < 1e-6f64 {
+            break;
+        }
+        s -= error / slope;
+    }
+
+    // ...but falls back to bisection on flat sections
+    let (mut low, mut high) = (0f64, 1f64);
+    s = t;
+    while high - low > 1e-12f64 {
+        if bezier(s, p1.0, p2.0) < t {
+            low = s;
+        } else {
+            high = s;
+        }
+        s = (low + high) / 2f64;
+    }
+    bezier(s, p1.1, p2.1)
+}
+
+/// Decodes a gamma-encoded sRGB channel into linear light
+fn srgb_to_linear(v: f64) -> f64 {
+    if v <= 0.04045f64 {
+        v / 12.92f64
+    } else {
+        ((v + 0.055f64) / 1.055f64).powf(2.4f64)
+    }
+}
+
+/// Encodes a linear light channel into gamma-encoded sRGB
+fn linear_to_srgb(v: f64) -> f64 {
+    if v <= 0.0031308f64 {
+        v * 12.92f64
+    } else {
+        1.055f64 * v.powf(1f64 / 2.4f64) - 0.055f64
+    }
+}
+
+/// Parses a CSS `<number>` or `<percentage>`, where `100%` is `reference`
+/// and `none` is `0`
+#[cfg(feature = "alloc")]
+fn css_number(token: &str, reference: f64) -> Result<f64, String> {
+    let (value, scale) = match token.strip_suffix('%') {
+        Some(value) => (value, reference / 100f64),
+        None if token.eq_ignore_ascii_case("none") => return Ok(0f64),
+        None => (token, 1f64),
+    };
+    match value.parse::<f64>() {
+        Ok(value) if value.is_finite() => Ok(value * scale),
+        _ => Err(format!("`{token}` is not a valid CSS number.")),
+    }
+}
+
+/// Parses a CSS `<hue>` into degrees, a bare number being degrees
+#[cfg(feature = "alloc")]
+fn css_hue(token: &str) -> Result<f64, String> {
+    let lowercase = token.to_ascii_lowercase();
+    let (value, scale) = [
+        ("deg", 1f64),
+        ("grad", 0.9f64),
+        ("rad", 180f64 / core::f64::consts::PI),
+        ("turn", 360f64),
+    ]
+    .iter()
+    .find_map(|&(unit, scale)| lowercase.strip_suffix(unit).map(|value| (value, scale)))
+    .unwrap_or((&lowercase, 1f64));
+    if value == "none" {
+        return Ok(0f64);
+    }
+    match value.parse::<f64>() {
+        Ok(value) if value.is_finite() => Ok(value * scale),
+        _ => Err(format!("`{token}` is not a valid CSS hue.")),
+    }
+}
+
+/// sRGB D65 XYZ to linear RGB, as used by the HSLuv reference
+const HSLUV_M: [[f64; 3]; 3] = [
+    [
+        3.240969941904521f64,
+        -1.537383177570093f64,
+        -0.498610760293f64,
+    ],
+    [
+        -0.96924363628087f64,
+        1.87596750150772f64,
+        0.041555057407175f64,
+    ],
+    [
+        0.055630079696993f64,
+        -0.20397695888897f64,
+        1.056971514242878f64,
+    ],
+];
+
+/// Linear RGB to sRGB D65 XYZ, as used by the HSLuv reference
+const HSLUV_M_INV: [[f64; 3]; 3] = [
+    [
+        0.41239079926595f64,
+        0.35758433938387f64,
+        0.18048078840183f64,
+    ],
+    [
+        0.21263900587151f64,
+        0.71516867876775f64,
+        0.072192315360733f64,
+    ],
+    [
+        0.019330818715591f64,
+        0.11919477979462f64,
+        0.95053215224966f64,
+    ],
+];
+
+const HSLUV_REF_U: f64 = 0.19783000664283f64;
+const HSLUV_REF_V: f64 = 0.46831999493879f64;
+const HSLUV_KAPPA: f64 = 903.2962962f64;
+const HSLUV_EPSILON: f64 = 0.0088564516f64;
+
+/// The six lines `(slope, intercept)` bounding the sRGB gamut in the
+/// CIELUV `(u, v)` plane at lightness `l`
+fn hsluv_bounds(l: f64) -> [(f64, f64); 6] {
+    let sub1 = (l + 16f64).powi(3) / 1560896f64;
+    let sub2 = if sub1 > HSLUV_EPSILON {
+        sub1
+    } else {
+        l / HSLUV_KAPPA
+    };
+    let mut bounds = [(0f64, 0f64); 6];
+    for (c, [m1, m2, m3]) in HSLUV_M.iter().enumerate() {
+        for t in 0..2 {
+            let t = t as f64;
+            let top1 = (284517f64 * m1 - 94839f64 * m3) * sub2;
+            let top2 =
+                (838422f64 * m3 + 769860f64 * m2 + 731718f64 * m1) * l * sub2 - 769860f64 * t * l;
+            let bottom = (632260f64 * m3 - 126452f64 * m2) * sub2 + 126452f64 * t;
+            bounds[c * 2 + t as usize] = (top1 / bottom, top2 / bottom);
+        }
+    }
+    bounds
+}
+
+/// The largest in-gamut CIELUV chroma at lightness `l` and hue `h` (degrees)
+fn hsluv_max_chroma(l: f64, h: f64) -> f64 {
+    let h = h.to_radians();
+    hsluv_bounds(l)
+        .iter()
+        .map(|(slope, intercept)| intercept / (h.sin() - slope * h.cos()))
+        .filter(|length| *length >= 0f64)
+        .fold(f64::INFINITY, f64::min)
+}
+
+/// The largest CIELUV chroma at lightness `l` that is in gamut for every hue
+fn hpluv_max_chroma(l: f64) -> f64 {
+    hsluv_bounds(l)
+        .iter()
+        .map(|(slope, intercept)| intercept.abs() / (slope * slope + 1f64).sqrt())
+        .fold(f64::INFINITY, f64::min)
+}
+
+/// The [CIEDE2000](https://en.wikipedia.org/wiki/Color_difference#CIEDE2000)
+/// colour difference between two CIELAB colours
+fn ciede2000((l1, a1, b1): (f64, f64, f64), (l2, a2, b2): (f64, f64, f64)) -> f64 {
+    let pow7 = |v: f64| v.powi(7);
+    let c_bar = (a1.hypot(b1) + a2.hypot(b2)) / 2f64;
+    let g = 0.5f64 * (1f64 - (pow7(c_bar) / (pow7(c_bar) + pow7(25f64))).sqrt());
+    let (a1, a2) = ((1f64 + g) * a1, (1f64 + g) * a2);
+    let (c1, c2) = (a1.hypot(b1), a2.hypot(b2));
+    let hue = |a: f64, b: f64| {
+        if a == 0f64 && b == 0f64 {
+            0f64
+        } else {
+            b.atan2(a).to_degrees().rem_euclid(360f64)
+        }
+    };
+    let (h1, h2) = (hue(a1, b1), hue(a2, b2));
+
+    let delta_l = l2 - l1;
+    let delta_c = c2 - c1;
+    let delta_h = if c1 * c2 == 0f64 {
+        0f64
+    } else if (h2 - h1).abs() <= 180f64 {
+        h2 - h1
+    } else if h2 - h1 > 180f64 {
+        h2 - h1 - 360f64
+    } else {
+        h2 - h1 + 360f64
+    };
+    let delta_h = 2f64 * (c1 * c2).sqrt() * (delta_h.to_radians() / 2f64).sin();
+
+    let l_bar = (l1 + l2) / 2f64;
+    let c_bar = (c1 + c2) / 2f64;
+    let h_bar = if c1 * c2 == 0f64 {
+        h1 + h2
+    } else if (h1 - h2).abs() <= 180f64 {
+        (h1 + h2) / 2f64
+    } else if h1 + h2 < 360f64 {
+        (h1 + h2 + 360f64) / 2f64
+    } else {
+        (h1 + h2 - 360f64) / 2f64
+    };
+    let cos = |degrees: f64| degrees.to_radians().cos();
+    let t = 1f64 - 0.17f64 * cos(h_bar - 30f64)
+        + 0.24f64 * cos(2f64 * h_bar)
+        + 0.32f64 * cos(3f64 * h_bar + 6f64)
+        - 0.20f64 * cos(4f64 * h_bar - 63f64);
+    let delta_theta = 30f64 * (-((h_bar - 275f64) / 25f64).powi(2)).exp();
+    let r_c = 2f64 * (pow7(c_bar) / (pow7(c_bar) + pow7(25f64))).sqrt();
+    let s_l = 1f64 + 0.015f64 * (l_bar - 50f64).powi(2) / (20f64 + (l_bar - 50f64).powi(2)).sqrt();
+    let s_c = 1f64 + 0.045f64 * c_bar;
+    let s_h = 1f64 + 0.015f64 * c_bar * t;
+    let r_t = -(2f64 * delta_theta).to_radians().sin() * r_c;
+
+    let (l, c, h) = (delta_l / s_l, delta_c / s_c, delta_h / s_h);
+    (l * l + c * c + h * h + r_t * c * h).sqrt()
+}
+
 impl Colour {
+    /// Solid white
+    pub const WHITE: Colour = Colour::grey(1f64);
+    /// Solid black
+    pub const BLACK: Colour = Colour::grey(0f64);
+    /// Solid red
+    pub const RED: Colour = Colour::red(1f64);
+    /// Solid green
+    pub const GREEN: Colour = Colour::green(1f64);
+    /// Solid blue
+    pub const BLUE: Colour = Colour::blue(1f64);
+    /// Solid yellow
+    pub const YELLOW: Colour = Colour::solid(1f64, 1f64, 0f64);
+    /// Solid cyan
+    pub const CYAN: Colour = Colour::solid(0f64, 1f64, 1f64);
+    /// Solid magenta
+    pub const MAGENTA: Colour = Colour::solid(1f64, 0f64, 1f64);
+    /// Transparent black, like `Colour::transparent()`
+    pub const TRANSPARENT: Colour = Colour::transparent();
+
     /// Creates a new Colour
-    pub fn new(r: f64, g: f64, b: f64, a: f64) -> Self {
+    pub const fn new(r: f64, g: f64, b: f64, a: f64) -> Self {
         Self { r, g, b, a }
     }
 
     /// Creates a solid greyscale colour
-    pub fn grey(grey: f64) -> Self {
+    pub const fn grey(grey: f64) -> Self {
         Self::solid(grey, grey, grey)
     }
 
     /// Creates a solid red colour
-    pub fn red(red: f64) -> Self {
+    pub const fn red(red: f64) -> Self {
         Self::solid(red, 0f64, 0f64)
     }
 
     /// Creates a solid green colour
-    pub fn green(green: f64) -> Self {
+    pub const fn green(green: f64) -> Self {
         Self::solid(0f64, green, 0f64)
     }
 
     /// Creates a solid blue colour
-    pub fn blue(blue: f64) -> Self {
+    pub const fn blue(blue: f64) -> Self {
         Self::solid(0f64, 0f64, blue)
     }
 
     /// Creates a colour by normalising `u8` values with
     /// `alpha = 1`
-    pub fn from_u8(r: u8, g: u8, b: u8) -> Self {
+    pub const fn from_u8(r: u8, g: u8, b: u8) -> Self {
         Self::solid(r as f64 / 255f64, g as f64 / 255f64, b as f64 / 255f64)
     }
 
     /// Creates a colour by normalising `u8` values
-    pub fn from_u8_rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
+    pub const fn from_u8_rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
         Self::from_u8(r, g, b).with_alpha(a as f64 / 255f64)
     }
 
+    /// Looks up a CSS named colour like `"rebeccapurple"`, ignoring case.
+    /// `"transparent"` is transparent black and any name that CSS doesn't
+    /// define is `None`.
+    ///
+    /// # Example
+    /// ```
+    /// use tcolour::Colour;
+    ///
+    /// assert_eq!(Colour::from_name("CornflowerBlue"), Some(Colour::from_u8(100, 149, 237)));
+    /// assert_eq!(Colour::from_name("transparent"), Some(Colour::transparent()));
+    /// assert_eq!(Colour::from_name("notacolour"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<Self> {
+        if name.eq_ignore_ascii_case("transparent") {
+            return Some(Self::transparent());
+        }
+        let lowercase = name.bytes().map(|b| b.to_ascii_lowercase());
+        CSS_NAMED_COLOURS
+            .binary_search_by(|(css_name, _)| css_name.bytes().cmp(lowercase.clone()))
+            .ok()
+            .map(|i| {
+                let (r, g, b) = CSS_NAMED_COLOURS[i].1;
+                Self::from_u8(r, g, b)
+            })
+    }
+
     /// Converts a u32 bit represenation of RGBA into
     /// a Colour
     pub fn from_u32_bitwise(bits: u32) -> Self {
@@ -152,10 +914,110 @@ impl Colour {
         )
     }
 
+    /// Gets the colour of an xterm-256 palette index: the 16 ANSI
+    /// colours, then the 6x6x6 colour cube and then the 24 step
+    /// greyscale ramp.
+    ///
+    /// # Example
+    /// ```
+    /// use tcolour::Colour;
+    ///
+    /// assert_eq!(Colour::from_xterm256(1), Colour::red(1.0));
+    /// assert_eq!(Colour::from_xterm256(196), Colour::red(1.0));
+    /// assert_eq!(Colour::from_xterm256(244), Colour::from_u8(128, 128, 128));
+    /// ```
+    pub fn from_xterm256(index: u8) -> Self {
+        match index {
+            0..16 => {
+                let (r, g, b) = ANSI_COLOURS[index as usize];
+                Self::from_u8(r, g, b)
+            }
+            16..232 => {
+                let index = (index - 16) as usize;
+                Self::from_u8(
+                    XTERM_CUBE_LEVELS[index / 36],
+                    XTERM_CUBE_LEVELS[(index % 36) / 6],
+                    XTERM_CUBE_LEVELS[index % 6],
+                )
+            }
+            232.. => {
+                let grey = 8 + (index - 232) * 10;
+                Self::from_u8(grey, grey, grey)
+            }
+        }
+    }
+
+    /// Finds the closest xterm-256 palette index in the colour cube or
+    /// greyscale ramp, ignoring alpha. The 16 ANSI colours are never
+    /// chosen as terminals tend to theme them.
+    ///
+    /// # Example
+    /// ```
+    /// use tcolour::Colour;
+    ///
+    /// assert_eq!(Colour::red(1.0).to_xterm256(), 196);
+    /// assert_eq!(Colour::grey(0.5).to_xterm256(), 244);
+    /// ```
+    pub fn to_xterm256(&self) -> u8 {
+        let (r, g, b) = self.clamped().as_u8();
+        let distance = |(r_to, g_to, b_to): (u8, u8, u8)| {
+            [(r, r_to), (g, g_to), (b, b_to)]
+                .into_iter()
+                .map(|(from, to)| (from as i32 - to as i32).pow(2))
+                .sum::<i32>()
+        };
+
+        let cube_level = |v: u8| match v {
+            0..48 => 0,
+            48..115 => 1,
+            _ => (v - 35) / 40,
+        };
+        let (r_level, g_level, b_level) = (cube_level(r), cube_level(g), cube_level(b));
+        let cube = (
+            XTERM_CUBE_LEVELS[r_level as usize],
+            XTERM_CUBE_LEVELS[g_level as usize],
+            XTERM_CUBE_LEVELS[b_level as usize],
+        );
+
+        let mean = (r as u32 + g as u32 + b as u32) / 3;
+        let grey_step = (mean.saturating_sub(3) / 10).min(23) as u8;
+        let grey = 8 + grey_step * 10;
+
+        if distance((grey, grey, grey)) < distance(cube) {
+            232 + grey_step
+        } else {
+            16 + 36 * r_level + 6 * g_level + b_level
+        }
+    }
+
+    /// The escape sequence setting the foreground to `.to_xterm256()`,
+    /// `\x1b[38;5;{n}m`, for terminals without truecolour support.
+    ///
+    /// # Example
+    /// ```
+    /// use tcolour::Colour;
+    ///
+    /// assert_eq!(Colour::red(1.0).ansi256_fg(), "\x1b[38;5;196m");
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn ansi256_fg(&self) -> String {
+        format!("\x1b[38;5;{}m", self.to_xterm256())
+    }
+
+    /// The escape sequence setting the background to `.to_xterm256()`,
+    /// `\x1b[48;5;{n}m`, see `.ansi256_fg()`.
+    #[cfg(feature = "alloc")]
+    pub fn ansi256_bg(&self) -> String {
+        format!("\x1b[48;5;{}m", self.to_xterm256())
+    }
+
     /// Converts the colour to a standard `u8` colour
     ///
     /// Note: does NOT composite the alpha into the colour,
     /// for alpha retrieval, use `.as_u8_rgba()`
+    ///
+    /// The channels are truncated, so `0.999` becomes `254`, use
+    /// `.to_u8_saturating()` to round to the nearest level.
     pub fn as_u8(&self) -> (u8, u8, u8) {
         (
             (self.r * 255f64) as u8,
@@ -173,40 +1035,104 @@ impl Colour {
         )
     }
 
+    /// Converts the colour to a standard `u8` colour by clamping each
+    /// channel between `[0, 1]` and rounding it to the nearest level,
+    /// unlike `.as_u8()` which truncates. `NaN` becomes `0`.
+    ///
+    /// Note: does NOT composite the alpha into the colour,
+    /// for alpha retrieval, use `.to_u8_rgba_saturating()`
+    ///
+    /// # Example
+    /// ```
+    /// use tcolour::Colour;
+    ///
+    /// let colour = Colour::solid(0.999, -0.5, 1.5);
+    /// assert_eq!(colour.to_u8_saturating(), (255, 0, 255));
+    /// assert_eq!(colour.as_u8(), (254, 0, 255));
+    /// ```
+    pub fn to_u8_saturating(&self) -> (u8, u8, u8) {
+        let (r, g, b, _) = self.to_u8_rgba_saturating();
+        (r, g, b)
+    }
+
+    /// Converts the colour, alpha included, to `u8`s like
+    /// `.to_u8_saturating()`
+    pub fn to_u8_rgba_saturating(&self) -> (u8, u8, u8, u8) {
+        let level = |v: f64| (v.clamp(0f64, 1f64) * 255f64).round() as u8;
+        (level(self.r), level(self.g), level(self.b), level(self.a))
+    }
+
+    /// Whether alpha is at least `1`, so nothing shows through
+    pub fn is_opaque(&self) -> bool {
+        self.a >= 1f64
+    }
+
+    /// Whether alpha is at most `0`, so the colour can't be seen
+    pub fn is_transparent(&self) -> bool {
+        self.a <= 0f64
+    }
+
+    /// Whether alpha is strictly between `0` and `1`, neither opaque nor
+    /// transparent
+    pub fn is_translucent(&self) -> bool {
+        !self.is_opaque() && !self.is_transparent() && !self.a.is_nan()
+    }
+
+    /// Whether alpha is within `epsilon` of being opaque, for colours
+    /// that have been through some floating point maths.
+    ///
+    /// # Example
+    /// ```
+    /// use tcolour::Colour;
+    ///
+    /// let almost = Colour::red(1.0).with_alpha(1.0 - 1e-12);
+    /// assert!(!almost.is_opaque());
+    /// assert!(almost.is_opaque_within(1e-9));
+    /// ```
+    pub fn is_opaque_within(&self, epsilon: f64) -> bool {
+        self.a >= 1f64 - epsilon
+    }
+
+    /// Whether alpha is within `epsilon` of being transparent, see
+    /// `.is_opaque_within()`.
+    pub fn is_transparent_within(&self, epsilon: f64) -> bool {
+        self.a <= epsilon
+    }
+
     pub fn is_normal(&self) -> bool {
         self.all_rgba(|v| (v.is_normal() || v == 0f64) && v.clamp(0f64, 1f64) == v)
     }
 
     /// Returns a Colour with alpha as `1`.
-    pub fn solid(r: f64, g: f64, b: f64) -> Self {
+    pub const fn solid(r: f64, g: f64, b: f64) -> Self {
         Self::new(r, g, b, 1f64)
     }
 
     /// Returns the Colour `RGBA(0, 0, 0, 0)`.
-    pub fn transparent() -> Self {
+    pub const fn transparent() -> Self {
         Self::new(0f64, 0f64, 0f64, 0f64)
     }
 
     /// Sets alpha to the given value and returns `Self`.
-    pub fn with_alpha(mut self, alpha: f64) -> Self {
+    pub const fn with_alpha(mut self, alpha: f64) -> Self {
         self.a = alpha;
         self
     }
 
     /// Sets red to the given value and returns `Self`.
-    pub fn with_red(mut self, red: f64) -> Self {
+    pub const fn with_red(mut self, red: f64) -> Self {
         self.r = red;
         self
     }
 
     /// Sets blue to the given value and returns `Self`.
-    pub fn with_blue(mut self, blue: f64) -> Self {
+    pub const fn with_blue(mut self, blue: f64) -> Self {
         self.b = blue;
         self
     }
 
     /// Sets green to the given value and returns `Self`.
-    pub fn with_green(mut self, green: f64) -> Self {
+    pub const fn with_green(mut self, green: f64) -> Self {
         self.g = green;
         self
     }
@@ -330,16 +1256,75 @@ impl Colour {
         )
     }
 
+    /// Creates a solid colour with uniformly random red, green and blue
+    /// channels between `[0, 1]`. `rng.random::<Colour>()` does the same.
+    ///
+    /// # Example
+    /// ```
+    /// use rand::SeedableRng;
+    /// use tcolour::Colour;
+    ///
+    /// let mut rng = rand::rngs::StdRng::seed_from_u64(0xbeef);
+    /// let colour = Colour::random(&mut rng);
+    /// assert_eq!(colour.a, 1.0);
+    /// ```
     #[cfg(feature = "rand")]
-    pub fn random() -> Self {
-        Self::new(
-            rand::random_range(0f64..=1f64),
-            rand::random_range(0f64..=1f64),
-            rand::random_range(0f64..=1f64),
-            rand::random_range(0f64..=1f64),
+    pub fn random<R: rand::Rng + ?Sized>(rng: &mut R) -> Self {
+        Self::solid(
+            rng.random_range(0f64..=1f64),
+            rng.random_range(0f64..=1f64),
+            rng.random_range(0f64..=1f64),
         )
     }
 
+    /// Creates a colour with every channel, including alpha, uniformly
+    /// random between `[0, 1]`.
+    #[cfg(feature = "rand")]
+    pub fn random_rgba<R: rand::Rng + ?Sized>(rng: &mut R) -> Self {
+        Self::random(rng).with_alpha(rng.random_range(0f64..=1f64))
+    }
+
+    /// The raw bit patterns of `[r, g, b, a]`, as `f64::to_bits()`, two
+    /// colours have the same bits only if they are exactly equal.
+    #[cfg(feature = "hash")]
+    pub fn to_bits(&self) -> [u64; 4] {
+        [self.r, self.g, self.b, self.a].map(f64::to_bits)
+    }
+
+    /// Gets a channel by index, `0..=3` being `r`, `g`, `b` and `a`,
+    /// for looping over channels generically. Any other index is `None`,
+    /// unlike indexing with `colour[i]` which panics.
+    ///
+    /// # Example
+    /// ```
+    /// use tcolour::Colour;
+    ///
+    /// let colour = Colour::new(0.1, 0.2, 0.3, 0.4);
+    /// assert_eq!(colour.channel(2), Some(0.3));
+    /// assert_eq!(colour[3], 0.4);
+    /// assert_eq!(colour.channel(4), None);
+    /// ```
+    pub fn channel(&self, i: usize) -> Option<f64> {
+        match i {
+            0 => Some(self.r),
+            1 => Some(self.g),
+            2 => Some(self.b),
+            3 => Some(self.a),
+            _ => None,
+        }
+    }
+
+    /// Gets a mutable reference to a channel by index, see `.channel()`
+    pub fn channel_mut(&mut self, i: usize) -> Option<&mut f64> {
+        match i {
+            0 => Some(&mut self.r),
+            1 => Some(&mut self.g),
+            2 => Some(&mut self.b),
+            3 => Some(&mut self.a),
+            _ => None,
+        }
+    }
+
     pub fn all<F: Fn(f64) -> bool>(&self, predicate: F) -> bool {
         predicate(self.r) && predicate(self.g) && predicate(self.b)
     }
@@ -355,78 +1340,398 @@ impl Colour {
     pub fn all_rgba_with<F: Fn(f64, f64) -> bool>(&self, other: Self, predicate: F) -> bool {
         self.all_with(other, &predicate) && predicate(self.a, other.a)
     }
+
+    /// Folds the red, green and blue channels, in that order, into an
+    /// accumulator
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::Colour;
+    ///
+    /// let colour = Colour::solid(0.1, 0.2, 0.3);
+    /// let sum_of_squares = colour.fold(0.0, |sum, v| sum + v * v);
+    /// assert_relative_eq!(sum_of_squares, 0.14);
+    /// ```
+    pub fn fold<T, F: Fn(T, f64) -> T>(&self, init: T, f: F) -> T {
+        f(f(f(init, self.r), self.g), self.b)
+    }
+
+    /// Folds the red, green, blue and alpha channels, in that order,
+    /// into an accumulator
+    pub fn fold_rgba<T, F: Fn(T, f64) -> T>(&self, init: T, f: F) -> T {
+        f(self.fold(init, &f), self.a)
+    }
+
     /// Blends two colours together using one of the many blend modes
     /// and then composites the blended colour onto the base colour
     /// using alpha compositing.
     ///
     /// The values are not necessarily normalised on return if `self`
-    /// or `other` are not normalised. They are often not normalised 
-    /// with `BlendMode::Addition`, `BlendMode::Subtract` and 
-    /// `BlendMode::Divide`; these are intended to be normalised by 
-    /// the user but in general use should be normalised with 
-    /// `.clamped()`. 
+    /// or `other` are not normalised. They are often not normalised
+    /// with `BlendMode::Addition`, `BlendMode::Subtract` and
+    /// `BlendMode::Divide` (as well as `BlendMode::LinearBurn` and
+    /// `BlendMode::LinearLight`); these are intended to be normalised by
+    /// the user but in general use should be normalised with
+    /// `.clamped()`.
     ///
     /// This treats `self` as the base layer and other as the
     /// blend layer, use `.blend_onto()` to swap this around
     pub fn blend(&self, other: Self, blend_mode: BlendMode) -> Self {
-        // Blend the RGB values first
-        let blended = match blend_mode {
-            BlendMode::Normal => other,
-            BlendMode::Addition => self + other,
-            BlendMode::Subtract => self - other,
-            BlendMode::Multiply => self * other,
-            BlendMode::Divide => self / other,
-            BlendMode::Darken => self.map_with(other, |base, blend| base.min(blend)),
-            BlendMode::Lighten => self.map_with(other, |base, blend| base.max(blend)),
-            BlendMode::Screen => -(-self * -other),
-            BlendMode::Overlay => self.map_with(other, |base, blend| {
-                if base < 0.5f64 {
-                    2f64 * blend * base
-                } else {
-                    1f64 - 2f64 * (1f64 - base) * (1f64 - blend)
-                }
-            }),
-            BlendMode::HardLight => other.blend(*self, BlendMode::Overlay),
-            BlendMode::SoftLight => self * -(-other * -other) + -self * other,
-        }
-        .cleaned();
-        // Compose the colours with the alpha
-        let alpha_composite = other.a + self.a * (1f64 - other.a);
-        ((blended * other.a + self * self.a * (1f64 - other.a)) / (alpha_composite))
-            .with_alpha(alpha_composite)
+        self.composite_blended(other, self.layered_rgb(other, blend_mode))
+            .1
     }
 
-    /// Blends two colours together using one of the many blend modes
-    /// and then composites the blended colour onto the base colour
-    /// using alpha compositing.
+    /// Blends the RGB channels of two colours like `.blend()` without
+    /// compositing the result onto the base colour, for when alpha is
+    /// handled separately such as in a shader. The channels are cleaned
+    /// like `.cleaned()` and the result keeps `self`'s alpha.
     ///
-    /// This treats `other` as the base layer and `self` as the
-    /// blend layer, use `.blend()` to swap this around
-    pub fn blend_onto(self, other: Self, blend_mode: BlendMode) -> Self {
-        other.blend(self, blend_mode)
-    }
-
-    /// Alpha compose the two colours together. This is the same
-    /// as blending with `BlendMode::Normal`
+    /// Neither alpha affects the channels, so `BlendMode::HardLight` is
+    /// the plain swapped overlay here, even though `.blend()` composites
+    /// it like `other.blend(*self, BlendMode::Overlay)`.
+    ///
+    /// # Example
     /// ```
     /// use approx::assert_relative_eq;
     /// use tcolour::{Colour, BlendMode};
     ///
-    /// let blend_layer = Colour::grey(0.5).with_alpha(0.3);
-    /// let base_layer = Colour::red(1.0);
+    /// let base = Colour::grey(0.5);
+    /// let blend = Colour::grey(0.5).with_alpha(0.25);
     ///
-    /// assert_relative_eq!(base_layer.compose(blend_layer), base_layer.blend(blend_layer, BlendMode::Normal));
-    /// assert_relative_eq!(base_layer.compose(blend_layer), blend_layer.compose_onto(base_layer));
+    /// assert_relative_eq!(base.blend_rgb(blend, BlendMode::Multiply), Colour::grey(0.25));
+    /// assert_relative_eq!(base.blend_rgb(Colour::grey(0.0), BlendMode::Divide), Colour::grey(1.0));
     /// ```
-    pub fn compose(&self, other: Self) -> Self {
-        self.blend(other, BlendMode::Normal)
+    pub fn blend_rgb(&self, other: Self, blend_mode: BlendMode) -> Self {
+        let [r, g, b] = self.blended_rgb(other, blend_mode).map(clean_channel);
+        Self::new(r, g, b, self.a)
     }
 
-    /// Alpha compose the two colours together. This is the same
-    /// as blending with `BlendMode::Normal`
-    /// ```
-    /// use approx::assert_relative_eq;
-    /// use tcolour::{Colour, BlendMode};
+    /// Blends two colours together exactly like `.blend()` but returns
+    /// `ColourError::NonFiniteChannel` if any channel of either colour
+    /// is `NaN` or infinite, instead of cleaning it away. Results of the
+    /// blend itself, like dividing by zero, are still cleaned.
+    ///
+    /// # Example
+    /// ```
+    /// use tcolour::{BlendMode, Colour, ColourError};
+    ///
+    /// let base = Colour::grey(0.5);
+    /// assert_eq!(
+    ///     base.blend_strict(Colour::grey(0.5), BlendMode::Multiply),
+    ///     Ok(base.blend(Colour::grey(0.5), BlendMode::Multiply))
+    /// );
+    /// assert_eq!(
+    ///     base.blend_strict(Colour::grey(f64::NAN), BlendMode::Multiply),
+    ///     Err(ColourError::NonFiniteChannel)
+    /// );
+    /// ```
+    pub fn blend_strict(&self, other: Self, blend_mode: BlendMode) -> Result<Self, ColourError> {
+        if self.all_rgba(f64::is_finite) && other.all_rgba(f64::is_finite) {
+            Ok(self.blend(other, blend_mode))
+        } else {
+            Err(ColourError::NonFiniteChannel)
+        }
+    }
+
+    /// Blends two colours together like `.blend()` but in linear light,
+    /// decoding both colours with `.to_linear()` first and encoding the
+    /// result with `.to_srgb()`. The alpha compositing also happens in
+    /// linear light, so translucent layers mix like light does.
+    ///
+    /// Multiplicative modes in particular look physically wrong in gamma
+    /// space, where midtones come out too dark and muddy.
+    ///
+    /// # Example
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::{Colour, BlendMode};
+    ///
+    /// let (black, white) = (Colour::grey(0.0), Colour::grey(1.0).with_alpha(0.5));
+    ///
+    /// // Half of white's light over black is brighter than 0.5 once encoded
+    /// assert_relative_eq!(black.blend(white, BlendMode::Normal), Colour::grey(0.5));
+    /// assert_relative_eq!(
+    ///     black.blend_linear(white, BlendMode::Normal),
+    ///     Colour::grey(0.5).to_srgb(),
+    /// );
+    /// ```
+    pub fn blend_linear(&self, other: Self, blend_mode: BlendMode) -> Self {
+        self.to_linear()
+            .blend(other.to_linear(), blend_mode)
+            .to_srgb()
+    }
+
+    /// Blends two colours together in the given `BlendSpace`, calling
+    /// `.blend()` for `BlendSpace::Srgb` and `.blend_linear()` for
+    /// `BlendSpace::Linear`.
+    ///
+    /// # Example
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::{BlendMode, BlendSpace, Colour};
+    ///
+    /// let grey = Colour::grey(0.5);
+    /// assert_relative_eq!(
+    ///     grey.blend_in(grey, BlendMode::Multiply, BlendSpace::Linear),
+    ///     grey.blend_linear(grey, BlendMode::Multiply),
+    /// );
+    /// ```
+    pub fn blend_in(&self, other: Self, blend_mode: BlendMode, space: BlendSpace) -> Self {
+        match space {
+            BlendSpace::Srgb => self.blend(other, blend_mode),
+            BlendSpace::Linear => self.blend_linear(other, blend_mode),
+        }
+    }
+
+    /// Blends two colours together exactly like `.blend()` but also
+    /// returns the blended colour from before it is composited onto
+    /// the base colour, as `(blended, composited)`.
+    ///
+    /// The blended colour carries the blend layer's alpha and isn't
+    /// cleaned, so a `NaN` from `BlendMode::Divide` for example shows
+    /// up here before it is replaced during compositing. Its channels
+    /// are those of `.blend_rgb()`, which don't depend on either alpha.
+    ///
+    /// # Example
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::{Colour, BlendMode};
+    ///
+    /// let base = Colour::grey(0.5);
+    /// let blend = Colour::grey(0.5).with_alpha(0.5);
+    /// let (blended, composited) = base.blend_components(blend, BlendMode::Multiply);
+    ///
+    /// assert_relative_eq!(blended, Colour::grey(0.25).with_alpha(0.5));
+    /// assert_relative_eq!(composited, Colour::grey(0.375));
+    /// ```
+    pub fn blend_components(&self, other: Self, blend_mode: BlendMode) -> (Self, Self) {
+        let (blended, _) = self.composite_blended(other, self.blended_rgb(other, blend_mode));
+        (blended, self.blend(other, blend_mode))
+    }
+
+    /// Blends two colours together like `.blend()` but clamps the
+    /// blended channels between `[0, 1]` before compositing them onto
+    /// the base colour. As long as the base colour is in range, so is
+    /// the result, so chained additive blends starting from an in range
+    /// colour stay in range, unlike `.blend()` where only the composite
+    /// could be clamped afterwards. An out of range base is composited
+    /// as it is, so isn't clamped.
+    ///
+    /// # Example
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::{Colour, BlendMode};
+    ///
+    /// let base = Colour::grey(0.8);
+    /// let blend = Colour::grey(0.6).with_alpha(0.5);
+    ///
+    /// assert_relative_eq!(base.blend(blend, BlendMode::Addition), Colour::grey(1.1));
+    /// assert_relative_eq!(base.blend_clamped(blend, BlendMode::Addition), Colour::grey(0.9));
+    /// ```
+    pub fn blend_clamped(&self, other: Self, blend_mode: BlendMode) -> Self {
+        let blended = self
+            .layered_rgb(other, blend_mode)
+            .map(|v| v.clamp(0f64, 1f64));
+        self.composite_blended(other, blended).1
+    }
+
+    /// Blends two colours together like `.blend()` but clips the blend
+    /// layer to the base layer, like "clip to layer below" in image
+    /// editors. The blend layer only shows where the base is opaque so
+    /// the result keeps the base's alpha, and a transparent base stays
+    /// transparent.
+    ///
+    /// Over an opaque base this is the same as `.blend()`.
+    ///
+    /// # Example
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::{Colour, BlendMode};
+    ///
+    /// let base = Colour::grey(1.0).with_alpha(0.5);
+    /// let clipped = base.blend_clipped(Colour::red(1.0), BlendMode::Normal);
+    ///
+    /// assert_relative_eq!(clipped, Colour::red(1.0).with_alpha(0.5));
+    /// assert_eq!(Colour::transparent().blend_clipped(Colour::red(1.0), BlendMode::Normal).a, 0.0);
+    /// ```
+    pub fn blend_clipped(&self, other: Self, blend_mode: BlendMode) -> Self {
+        let [r, g, b] = self.blended_rgb(other, blend_mode);
+        let compose =
+            |base: f64, blended: f64| clean_channel(blended) * other.a + base * (1f64 - other.a);
+        Self::new(
+            compose(self.r, r),
+            compose(self.g, g),
+            compose(self.b, b),
+            self.a,
+        )
+    }
+
+    /// Blends two colours together like `.blend()` with the blend layer
+    /// at `opacity`, a layer opacity on top of `other`'s own alpha.
+    /// `opacity` is clamped between `[0, 1]`, `0` leaves `self` as it
+    /// was and `1` is the same as `.blend()`.
+    ///
+    /// # Example
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::{Colour, BlendMode};
+    ///
+    /// let (base, blend) = (Colour::grey(1.0), Colour::red(1.0));
+    ///
+    /// assert_relative_eq!(
+    ///     base.blend_with_opacity(blend, BlendMode::Normal, 0.25),
+    ///     base.blend(blend.with_alpha(0.25), BlendMode::Normal)
+    /// );
+    /// ```
+    pub fn blend_with_opacity(&self, other: Self, blend_mode: BlendMode, opacity: f64) -> Self {
+        let opacity = opacity.clamp(0f64, 1f64);
+        if opacity == 0f64 {
+            return *self;
+        }
+        self.blend(other.with_alpha(other.a * opacity), blend_mode)
+    }
+
+    /// Blends the RGB channels of two colours with `blend_mode`, before
+    /// compositing
+    fn blended_rgb(&self, other: Self, blend_mode: BlendMode) -> [f64; 3] {
+        let rgb = |colour: Colour| [colour.r, colour.g, colour.b];
+        match blend_mode {
+            BlendMode::Normal => rgb(other),
+            BlendMode::Addition => blend_channels(self, &other, |base, blend| base + blend),
+            BlendMode::Subtract => blend_channels(self, &other, |base, blend| base - blend),
+            BlendMode::Multiply => blend_channels(self, &other, |base, blend| base * blend),
+            BlendMode::Divide => blend_channels(self, &other, |base, blend| base / blend),
+            BlendMode::Darken => blend_channels(self, &other, f64::min),
+            BlendMode::Lighten => blend_channels(self, &other, f64::max),
+            BlendMode::Screen => blend_channels(self, &other, |base, blend| {
+                1f64 - (1f64 - base) * (1f64 - blend)
+            }),
+            BlendMode::Overlay => blend_channels(self, &other, overlay),
+            BlendMode::HardLight => {
+                blend_channels(self, &other, |base, blend| overlay(blend, base))
+            }
+            BlendMode::SoftLight => blend_channels(self, &other, |base, blend| {
+                base * (1f64 - (1f64 - blend) * (1f64 - blend)) + (1f64 - base) * blend
+            }),
+            BlendMode::LinearBurn => {
+                blend_channels(self, &other, |base, blend| base + blend - 1f64)
+            }
+            BlendMode::LinearLight => {
+                blend_channels(self, &other, |base, blend| base + 2f64 * blend - 1f64)
+            }
+            BlendMode::VividLight => blend_channels(self, &other, |base, blend| {
+                if blend <= 0.5f64 {
+                    colour_burn(base, 2f64 * blend)
+                } else {
+                    colour_dodge(base, 2f64 * (blend - 0.5f64))
+                }
+            }),
+            BlendMode::PinLight => blend_channels(self, &other, |base, blend| {
+                if blend <= 0.5f64 {
+                    base.min(2f64 * blend)
+                } else {
+                    base.max(2f64 * blend - 1f64)
+                }
+            }),
+            BlendMode::Hue => rgb(set_lum(set_sat(other, sat(*self)), lum(*self))),
+            BlendMode::Saturation => rgb(set_lum(set_sat(*self, sat(other)), lum(*self))),
+            BlendMode::Color => rgb(set_lum(other, lum(*self))),
+            BlendMode::Luminosity => rgb(set_lum(*self, lum(other))),
+        }
+    }
+
+    /// The channels `.blend()` composites onto `self`, which are those of
+    /// `.blended_rgb()` except for `BlendMode::HardLight`. That has always
+    /// composited like `other.blend(*self, BlendMode::Overlay)`, so its
+    /// swapped overlay is pre-composited with both alphas here
+    fn layered_rgb(&self, other: Self, blend_mode: BlendMode) -> [f64; 3] {
+        match blend_mode {
+            BlendMode::HardLight => {
+                let alpha_composite = self.a + other.a * (1f64 - self.a);
+                blend_channels(self, &other, |base, blend| {
+                    (clean_channel(overlay(blend, base)) * self.a
+                        + blend * other.a * (1f64 - self.a))
+                        / alpha_composite
+                })
+            }
+            _ => self.blended_rgb(other, blend_mode),
+        }
+    }
+
+    /// Composites the `blended` channels of `.layered_rgb()` onto `self`
+    /// with `other`'s alpha, returning `(blended, composited)`
+    fn composite_blended(&self, other: Self, [r, g, b]: [f64; 3]) -> (Self, Self) {
+        // Compose the colours with the alpha
+        let alpha_composite = other.a + self.a * (1f64 - other.a);
+        let compose = |base: f64, blended: f64| {
+            (clean_channel(blended) * other.a + base * self.a * (1f64 - other.a)) / alpha_composite
+        };
+        (
+            Self::new(r, g, b, other.a),
+            Self::new(
+                compose(self.r, r),
+                compose(self.g, g),
+                compose(self.b, b),
+                alpha_composite,
+            ),
+        )
+    }
+
+    /// Blends two colours together using one of the many blend modes
+    /// and then composites the blended colour onto the base colour
+    /// using alpha compositing.
+    ///
+    /// This treats `other` as the base layer and `self` as the
+    /// blend layer, use `.blend()` to swap this around
+    pub fn blend_onto(self, other: Self, blend_mode: BlendMode) -> Self {
+        other.blend(self, blend_mode)
+    }
+
+    /// Blends `self` onto `background` like `.blend_onto()`, treating
+    /// the background as opaque whatever its alpha, so the result is
+    /// always opaque. This is for exporting to formats without an alpha
+    /// channel.
+    ///
+    /// # Example
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::{Colour, BlendMode};
+    ///
+    /// let translucent_red = Colour::red(1.0).with_alpha(0.5);
+    /// let flattened = translucent_red.composite_over_solid(Colour::grey(1.0), BlendMode::Multiply);
+    ///
+    /// assert_relative_eq!(flattened, Colour::solid(1.0, 0.5, 0.5));
+    /// ```
+    pub fn composite_over_solid(&self, background: Colour, blend_mode: BlendMode) -> Self {
+        background
+            .with_alpha(1f64)
+            .blend(*self, blend_mode)
+            .with_alpha(1f64)
+    }
+
+    /// Alpha compose the two colours together. This is the same
+    /// as blending with `BlendMode::Normal`
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::{Colour, BlendMode};
+    ///
+    /// let blend_layer = Colour::grey(0.5).with_alpha(0.3);
+    /// let base_layer = Colour::red(1.0);
+    ///
+    /// assert_relative_eq!(base_layer.compose(blend_layer), base_layer.blend(blend_layer, BlendMode::Normal));
+    /// assert_relative_eq!(base_layer.compose(blend_layer), blend_layer.compose_onto(base_layer));
+    /// ```
+    pub fn compose(&self, other: Self) -> Self {
+        self.blend(other, BlendMode::Normal)
+    }
+
+    /// Alpha compose the two colours together. This is the same
+    /// as blending with `BlendMode::Normal`
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::{Colour, BlendMode};
     ///
     /// let blend_layer = Colour::grey(0.5).with_alpha(0.3);
     /// let base_layer = Colour::red(1.0);
@@ -438,16 +1743,112 @@ impl Colour {
         self.blend_onto(other, BlendMode::Normal)
     }
 
+    /// Alpha composes a stack of layers ordered from the bottom up,
+    /// the same as folding them with `.compose()`. Everything below
+    /// the topmost opaque layer is hidden by it, so compositing starts
+    /// there and lower layers aren't looked at. An empty stack is
+    /// `Colour::transparent()`.
+    ///
+    /// # Example
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::Colour;
+    ///
+    /// let layers = [
+    ///     Colour::red(1.0),
+    ///     Colour::blue(1.0),
+    ///     Colour::grey(1.0).with_alpha(0.5),
+    /// ];
+    ///
+    /// assert_relative_eq!(Colour::composite_stack(&layers), Colour::solid(0.5, 0.5, 1.0));
+    /// ```
+    pub fn composite_stack(layers: &[Colour]) -> Self {
+        let bottom = layers.iter().rposition(Colour::is_opaque).unwrap_or(0);
+        match layers.get(bottom..) {
+            Some([first, rest @ ..]) => {
+                rest.iter().fold(*first, |base, &layer| base.compose(layer))
+            }
+            _ => Self::transparent(),
+        }
+    }
+
     /// Linearly interpolate between two colours
     pub fn lerp(&self, other: Self, t: f64) -> Self {
         self + (other - self) * t
     }
 
+    /// Linearly interpolate between two colours after easing `t` with
+    /// the CSS timing function `cubic-bezier(p1.0, p1.1, p2.0, p2.1)`,
+    /// `t` is clamped between `[0, 1]`.
+    ///
+    /// # Example
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::Colour;
+    ///
+    /// let (black, white) = (Colour::grey(0.0), Colour::grey(1.0));
+    /// // CSS `ease`
+    /// let eased = black.lerp_cubic_bezier(white, 0.5, (0.25, 0.1), (0.25, 1.0));
+    ///
+    /// assert_relative_eq!(eased, Colour::grey(0.8024), epsilon = 1e-4);
+    /// ```
+    pub fn lerp_cubic_bezier(&self, other: Self, t: f64, p1: (f64, f64), p2: (f64, f64)) -> Self {
+        self.lerp(other, cubic_bezier(t, p1, p2))
+    }
+
     /// Gets the highest channel
     pub fn max_channel(&self) -> f64 {
         self.r.max(self.g.max(self.b.max(self.a)))
     }
 
+    /// The unweighted sum of the red, green and blue channels, a cheap
+    /// intensity for thresholding or sorting where perception doesn't
+    /// matter. Use `.luma()` or `.luminance()` for perceived brightness.
+    pub fn rgb_sum(&self) -> f64 {
+        self.r + self.g + self.b
+    }
+
+    /// The unweighted mean of the red, green and blue channels, see
+    /// `.rgb_sum()`.
+    pub fn rgb_mean(&self) -> f64 {
+        self.rgb_sum() / 3f64
+    }
+
+    /// The euclidean distance between the red, green and blue channels
+    /// of two colours, ignoring alpha. This is cheap but doesn't follow
+    /// perception, use `.delta_e()` for that.
+    ///
+    /// # Example
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::Colour;
+    ///
+    /// assert_relative_eq!(Colour::grey(0.0).distance(&Colour::grey(1.0)), 3f64.sqrt());
+    /// ```
+    pub fn distance(&self, other: &Colour) -> f64 {
+        let (r, g, b) = (self.r - other.r, self.g - other.g, self.b - other.b);
+        (r * r + g * g + b * b).sqrt()
+    }
+
+    /// The euclidean distance between all four channels of two colours,
+    /// see `.distance()`.
+    pub fn distance_rgba(&self, other: &Colour) -> f64 {
+        let (r, g, b, a) = (
+            self.r - other.r,
+            self.g - other.g,
+            self.b - other.b,
+            self.a - other.a,
+        );
+        (r * r + g * g + b * b + a * a).sqrt()
+    }
+
+    /// The manhattan distance, the sum of the absolute differences,
+    /// between the red, green and blue channels of two colours, ignoring
+    /// alpha.
+    pub fn manhattan(&self, other: &Colour) -> f64 {
+        (self.r - other.r).abs() + (self.g - other.g).abs() + (self.b - other.b).abs()
+    }
+
     /// Gets the lowest channel
     pub fn min_channel(&self) -> f64 {
         self.r.min(self.g.min(self.b.min(self.a)))
@@ -501,11 +1902,71 @@ impl Colour {
         });
     }
 
+    /// All `NaN`, `inf` and [subnormal](https://en.wikipedia.org/wiki/Subnormal_number)
+    /// values become `fallback`, like `.cleaned()` but with the
+    /// replacement of your choosing.
+    ///
+    /// Returns the result.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tcolour::Colour;
+    ///
+    /// let invalid_colour = Colour::grey(0.5) / 0.0;
+    ///
+    /// assert_eq!(invalid_colour.cleaned_to(0.0), Colour::grey(0.0));
+    /// ```
+    pub fn cleaned_to(&self, fallback: f64) -> Self {
+        self.map_rgba(|v| {
+            if !(v.is_normal() || v == 0f64) {
+                fallback
+            } else {
+                v
+            }
+        })
+    }
+
+    /// `NaN` and `inf` values become `1` like `.cleaned()`, but
+    /// [subnormal](https://en.wikipedia.org/wiki/Subnormal_number)
+    /// values are flushed to `0` rather than becoming white, as they are
+    /// legitimately tiny.
+    ///
+    /// Returns the result.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tcolour::Colour;
+    ///
+    /// let tiny = Colour::grey(1e-310);
+    ///
+    /// assert_eq!(tiny.cleaned(), Colour::grey(1.0));
+    /// assert_eq!(tiny.sanitised(), Colour::grey(0.0));
+    /// assert_eq!((tiny / 0.0).sanitised(), Colour::grey(1.0));
+    /// ```
+    pub fn sanitised(&self) -> Self {
+        self.map_rgba(|v| {
+            if !v.is_finite() {
+                1f64
+            } else if !v.is_normal() && v != 0f64 {
+                0f64
+            } else {
+                v
+            }
+        })
+    }
+
     /// Clamps all values to between `[0, 1]`, returns the result.
     pub fn clamped(&self) -> Self {
         self.map_rgba(|v| v.clamp(0f64, 1f64))
     }
 
+    /// An alias of `.clamped()`
+    pub fn clamp01(&self) -> Self {
+        self.clamped()
+    }
+
     /// Clamps all values to between `[0, 1]`, modifies `self` in place.
     pub fn clamp(&mut self) {
         self.apply_rgba(|v| *v = v.clamp(0f64, 1f64));
@@ -609,284 +2070,3535 @@ impl Colour {
         self.normalise();
         self
     }
-}
 
-impl From<[f64; 3]> for Colour {
-    fn from(value: [f64; 3]) -> Self {
-        Colour::solid(value[0], value[1], value[2])
-    }
-}
-impl From<[f64; 4]> for Colour {
-    fn from(value: [f64; 4]) -> Self {
-        Colour::new(value[0], value[1], value[2], value[3])
-    }
-}
-impl From<(f64, f64, f64)> for Colour {
-    fn from((r, g, b): (f64, f64, f64)) -> Self {
-        Colour::solid(r, g, b)
+    /// Decodes gamma-encoded sRGB into linear light with the standard
+    /// sRGB transfer function, leaving alpha alone. Blending and
+    /// averaging linear colours behaves physically, use `.to_srgb()`
+    /// to encode the result again.
+    ///
+    /// # Example
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::Colour;
+    ///
+    /// let colour = Colour::new(0.2, 0.5, 0.9, 0.3);
+    /// assert_relative_eq!(colour.to_linear().to_srgb(), colour, epsilon = 1e-12);
+    /// ```
+    pub fn to_linear(&self) -> Self {
+        self.map(srgb_to_linear)
     }
-}
-impl From<(f64, f64, f64, f64)> for Colour {
-    fn from((r, g, b, a): (f64, f64, f64, f64)) -> Self {
-        Colour::new(r, g, b, a)
+
+    /// Encodes linear light into gamma-encoded sRGB with the standard
+    /// sRGB transfer function, leaving alpha alone. The inverse of
+    /// `.to_linear()`.
+    ///
+    /// # Example
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::Colour;
+    ///
+    /// assert_relative_eq!(Colour::grey(0.5).to_srgb(), Colour::grey(0.7353569830524495));
+    /// ```
+    pub fn to_srgb(&self) -> Self {
+        self.map(linear_to_srgb)
     }
-}
 
-impl From<[u8; 3]> for Colour {
-    fn from(value: [u8; 3]) -> Self {
+    /// Raises each channel to `power`, leaving alpha alone, for quick
+    /// tone curve tweaks. This is a raw power curve rather than the
+    /// piecewise sRGB transfer function of `.to_linear()` and
+    /// `.to_srgb()`, although `.gamma(1.0 / 2.2)` is a rough encode.
+    /// Powers below `1` brighten and above `1` darken.
+    ///
+    /// # Example
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::Colour;
+    ///
+    /// let colour = Colour::new(0.5, 0.25, 1.0, 0.5);
+    /// assert_relative_eq!(colour.gamma(2.0), Colour::new(0.25, 0.0625, 1.0, 0.5));
+    /// ```
+    pub fn gamma(&self, power: f64) -> Self {
+        self.map(|v| v.powf(power))
+    }
+
+    /// Raises each channel to `power` in place, leaving alpha alone, see
+    /// `.gamma()`.
+    pub fn apply_gamma(&mut self, power: f64) {
+        *self = self.gamma(power)
+    }
+
+    /// The [WCAG relative luminance](https://www.w3.org/TR/WCAG21/#dfn-relative-luminance)
+    /// of the colour, from `0` for black to `1` for white, ignoring alpha.
+    ///
+    /// The channels are treated as gamma-encoded sRGB, so they are
+    /// linearised before weighting them by `0.2126`, `0.7152` and `0.0722`.
+    ///
+    /// # Example
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::Colour;
+    ///
+    /// assert_relative_eq!(Colour::grey(1.0).luminance(), 1.0);
+    /// assert_relative_eq!(Colour::green(1.0).luminance(), 0.7152);
+    /// ```
+    pub fn luminance(&self) -> f64 {
+        self.to_linear().luma()
+    }
+
+    /// The relative luminance of a colour whose channels are already
+    /// linear light, such as the result of `.to_linear()`, ignoring
+    /// alpha. This only weights the channels by `0.2126`, `0.7152` and
+    /// `0.0722`, `.luminance()` decodes them from sRGB first which is
+    /// wrong for linear colours.
+    ///
+    /// The weights are the same as `.luma()`'s, the two only differ in
+    /// whether the channels are expected to be linear or gamma-encoded.
+    ///
+    /// # Example
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::Colour;
+    ///
+    /// let colour = Colour::solid(0.2, 0.4, 0.6);
+    /// assert_relative_eq!(colour.to_linear().luminance_linear(), colour.luminance());
+    /// ```
+    pub fn luminance_linear(&self) -> f64 {
+        self.luma()
+    }
+
+    /// The [Rec. 709 luma](https://en.wikipedia.org/wiki/Luma_(video))
+    /// of the colour, ignoring alpha.
+    ///
+    /// Unlike `.luminance()` the weights are applied directly to the
+    /// gamma-encoded channels, which is cheaper and what video and most
+    /// image editors use for perceived brightness, but it isn't a
+    /// physical measure of light.
+    ///
+    /// # Example
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::Colour;
+    ///
+    /// assert_relative_eq!(Colour::green(1.0).luma(), 0.7152);
+    /// // Luminance is lower for midtones as it is linear light
+    /// assert_relative_eq!(Colour::grey(0.5).luma(), 0.5);
+    /// assert!(Colour::grey(0.5).luminance() < 0.5);
+    /// ```
+    pub fn luma(&self) -> f64 {
+        0.2126f64 * self.r + 0.7152f64 * self.g + 0.0722f64 * self.b
+    }
+
+    /// Converts to a grey with every channel set to `.luma()`,
+    /// keeping alpha.
+    ///
+    /// # Example
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::Colour;
+    ///
+    /// let orange = Colour::new(1.0, 0.5, 0.0, 0.5);
+    /// assert_relative_eq!(orange.grayscale(), Colour::grey(0.5702).with_alpha(0.5));
+    /// ```
+    pub fn grayscale(&self) -> Self {
+        Self::grey(self.luma()).with_alpha(self.a)
+    }
+
+    /// Sets the alpha to `.luma()`, keeping the colour, so bright areas
+    /// become opaque and dark areas transparent. With `invert` the alpha
+    /// is `1 - luma` instead. A grey's alpha is its grey value, turning
+    /// a greyscale map into a mask for luma keying.
+    ///
+    /// # Example
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::Colour;
+    ///
+    /// assert_relative_eq!(Colour::grey(0.25).luma_to_alpha(false), Colour::grey(0.25).with_alpha(0.25));
+    /// assert_relative_eq!(Colour::grey(0.25).luma_to_alpha(true), Colour::grey(0.25).with_alpha(0.75));
+    /// ```
+    pub fn luma_to_alpha(&self, invert: bool) -> Self {
+        let luma = self.luma();
+        self.with_alpha(if invert { 1f64 - luma } else { luma })
+    }
+
+    /// Moves the colour away from its `.grayscale()` grey by `amount`,
+    /// where `0` leaves it as is and `1` doubles its distance from the
+    /// grey. Alpha is kept.
+    ///
+    /// The result is not clamped, vivid colours can be pushed outside of
+    /// `[0, 1]` so use `.clamped()` if necessary.
+    ///
+    /// # Example
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::Colour;
+    ///
+    /// let muted = Colour::solid(0.6, 0.4, 0.4);
+    /// assert_relative_eq!(muted.saturated(0.5).desaturated(1.0), muted.grayscale());
+    /// assert!(muted.saturated(0.5).r > muted.r);
+    /// ```
+    pub fn saturated(&self, amount: f64) -> Self {
+        let grey = self.luma();
+        self.map(|v| v + (v - grey) * amount)
+    }
+
+    /// Moves the colour towards its `.grayscale()` grey by `amount`,
+    /// where `0` leaves it as is and `1` is the grey. Alpha is kept.
+    ///
+    /// # Example
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::Colour;
+    ///
+    /// // Halfway between red and its luma of 0.2126
+    /// assert_relative_eq!(
+    ///     Colour::red(1.0).desaturated(0.5),
+    ///     Colour::solid(0.6063, 0.1063, 0.1063)
+    /// );
+    /// ```
+    pub fn desaturated(&self, amount: f64) -> Self {
+        let grey = self.luma();
+        self.map(|v| v + (grey - v) * amount)
+    }
+
+    /// The [WCAG contrast ratio](https://www.w3.org/TR/WCAG21/#dfn-contrast-ratio)
+    /// `(L1 + 0.05) / (L2 + 0.05)` between two colours, where `L1` is the
+    /// luminance of the lighter colour. This ranges from `1` for identical
+    /// luminances to `21` for black and white, and is the same either way
+    /// around.
+    ///
+    /// Like `.luminance()`, the channels are treated as gamma-encoded sRGB
+    /// and alpha is ignored, so composite translucent colours first.
+    ///
+    /// # Example
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::Colour;
+    ///
+    /// let (black, white) = (Colour::grey(0.0), Colour::grey(1.0));
+    /// assert_relative_eq!(black.contrast_ratio(&white), 21.0);
+    /// // WCAG AA asks for at least 4.5:1 for body text
+    /// assert!(Colour::grey(0.45).contrast_ratio(&white) > 4.5);
+    /// ```
+    pub fn contrast_ratio(&self, other: &Colour) -> f64 {
+        let (a, b) = (self.luminance(), other.luminance());
+        (a.max(b) + 0.05f64) / (a.min(b) + 0.05f64)
+    }
+
+    /// Lightens or darkens the colour with `.with_lightness()` as little
+    /// as possible until its `.contrast_ratio()` against `background` is
+    /// at least `target_ratio`, keeping the hue, saturation and alpha.
+    ///
+    /// A colour at least as bright as the background is lightened and
+    /// a darker one darkened, unless only the other way can reach the
+    /// target. If neither can, the best achievable of black and white is
+    /// returned. A colour that already meets the target is unchanged.
+    ///
+    /// # Example
+    /// ```
+    /// use tcolour::Colour;
+    ///
+    /// let background = Colour::grey(0.5);
+    /// let text = Colour::solid(0.4, 0.45, 0.6).ensure_contrast(&background, 4.5);
+    ///
+    /// assert!(text.contrast_ratio(&background) >= 4.5);
+    /// assert!(text.luminance() < background.luminance());
+    /// ```
+    pub fn ensure_contrast(&self, background: &Colour, target_ratio: f64) -> Self {
+        if self.contrast_ratio(background) >= target_ratio {
+            return *self;
+        }
+        let meets = |l: f64| self.with_lightness(l).contrast_ratio(background) >= target_ratio;
+        let extremes = if self.luminance() >= background.luminance() {
+            [1f64, 0f64]
+        } else {
+            [0f64, 1f64]
+        };
+        let Some(extreme) = extremes.into_iter().find(|&l| meets(l)) else {
+            let (white, black) = (self.with_lightness(1f64), self.with_lightness(0f64));
+            return if white.contrast_ratio(background) > black.contrast_ratio(background) {
+                white
+            } else {
+                black
+            };
+        };
+        // Luminance is monotonic in the lightness, so bisect between the
+        // current lightness, which falls short, and the extreme that meets it
+        let (_, _, mut short) = self.to_hsl();
+        let mut meeting = extreme;
+        for _ in 0..64 {
+            let mid = (short + meeting) / 2f64;
+            if meets(mid) {
+                meeting = mid
+            } else {
+                short = mid
+            }
+        }
+        self.with_lightness(meeting)
+    }
+
+    /// Returns whichever whole colour has the higher `.luminance()`,
+    /// `self` if they are equally bright. Unlike `BlendMode::Lighten`
+    /// this never mixes the channels of the two colours.
+    ///
+    /// # Example
+    /// ```
+    /// use tcolour::Colour;
+    ///
+    /// let (yellow, blue) = (Colour::solid(0.6, 0.6, 0.0), Colour::solid(0.0, 0.0, 1.0));
+    /// assert_eq!(yellow.lighter_of(blue), yellow);
+    /// ```
+    pub fn lighter_of(self, other: Self) -> Self {
+        if other.luminance() > self.luminance() {
+            other
+        } else {
+            self
+        }
+    }
+
+    /// Returns whichever whole colour has the lower `.luminance()`,
+    /// `self` if they are equally bright. Unlike `BlendMode::Darken`
+    /// this never mixes the channels of the two colours.
+    ///
+    /// # Example
+    /// ```
+    /// use tcolour::Colour;
+    ///
+    /// let (yellow, blue) = (Colour::solid(0.6, 0.6, 0.0), Colour::solid(0.0, 0.0, 1.0));
+    /// assert_eq!(yellow.darker_of(blue), blue);
+    /// ```
+    pub fn darker_of(self, other: Self) -> Self {
+        if other.luminance() < self.luminance() {
+            other
+        } else {
+            self
+        }
+    }
+
+    /// Converts to [OKLab](https://bottosson.github.io/posts/oklab/)
+    /// as `(L, a, b)`, ignoring alpha. The channels are linearised and
+    /// taken through Björn Ottosson's LMS matrices and cube root, so
+    /// black is `L = 0`, white is `L = 1` and greys have `a = b = 0`.
+    ///
+    /// Equal steps in OKLab look like equal steps in colour, which makes
+    /// it a good space to interpolate in.
+    ///
+    /// # Example
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::Colour;
+    ///
+    /// let (l, a, b) = Colour::grey(1.0).to_oklab();
+    /// assert_relative_eq!(l, 1.0, epsilon = 1e-6);
+    /// assert_relative_eq!(a, 0.0, epsilon = 1e-6);
+    /// assert_relative_eq!(b, 0.0, epsilon = 1e-6);
+    ///
+    /// for colour in [Colour::red(1.0), Colour::solid(0.2, 0.5, 0.8), Colour::grey(0.3)] {
+    ///     let (l, a, b) = colour.to_oklab();
+    ///     assert_relative_eq!(Colour::from_oklab(l, a, b), colour, epsilon = 1e-6);
+    /// }
+    /// ```
+    pub fn to_oklab(&self) -> (f64, f64, f64) {
+        let (r, g, b) = (
+            srgb_to_linear(self.r),
+            srgb_to_linear(self.g),
+            srgb_to_linear(self.b),
+        );
+        let l = (0.4122214708f64 * r + 0.5363325363f64 * g + 0.0514459929f64 * b).cbrt();
+        let m = (0.2119034982f64 * r + 0.6806995451f64 * g + 0.1073969566f64 * b).cbrt();
+        let s = (0.0883024619f64 * r + 0.2817188376f64 * g + 0.6299787005f64 * b).cbrt();
+        (
+            0.2104542553f64 * l + 0.7936177850f64 * m - 0.0040720468f64 * s,
+            1.9779984951f64 * l - 2.4285922050f64 * m + 0.4505937099f64 * s,
+            0.0259040371f64 * l + 0.7827717662f64 * m - 0.8086757660f64 * s,
+        )
+    }
+
+    /// Creates a solid colour from [OKLab](https://bottosson.github.io/posts/oklab/),
+    /// the inverse of `.to_oklab()`.
+    ///
+    /// The result is not clamped, OKLab can describe colours outside of
+    /// the sRGB gamut so use `.clamped()` if necessary.
+    pub fn from_oklab(l: f64, a: f64, b: f64) -> Self {
+        let l_ = (l + 0.3963377774f64 * a + 0.2158037573f64 * b).powi(3);
+        let m_ = (l - 0.1055613458f64 * a - 0.0638541728f64 * b).powi(3);
+        let s_ = (l - 0.0894841775f64 * a - 1.2914855480f64 * b).powi(3);
+        Self::solid(
+            linear_to_srgb(4.0767416621f64 * l_ - 3.3077115913f64 * m_ + 0.2309699292f64 * s_),
+            linear_to_srgb(-1.2684380046f64 * l_ + 2.6097574011f64 * m_ - 0.3413193965f64 * s_),
+            linear_to_srgb(-0.0041960863f64 * l_ - 0.7034186147f64 * m_ + 1.7076147010f64 * s_),
+        )
+    }
+
+    /// Converts to OKLCH as `(L, C, h)`, the polar form of
+    /// `.to_oklab()` with the hue `h` in degrees within `[0, 360)`,
+    /// ignoring alpha.
+    ///
+    /// Rotating `h` while keeping `L` and `C` changes the hue without
+    /// changing the perceived lightness. Achromatic colours have no
+    /// meaningful hue so it is `0` when the chroma is below `1e-6`.
+    ///
+    /// # Example
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::Colour;
+    ///
+    /// let (_, _, h) = Colour::red(1.0).to_oklch();
+    /// assert_relative_eq!(h, 29.23, epsilon = 1e-2);
+    ///
+    /// // The same lightness and chroma a third of the way round
+    /// let (l, c, h) = Colour::solid(0.55, 0.5, 0.5).to_oklch();
+    /// let (rotated_l, rotated_c, _) = Colour::from_oklch(l, c, h + 120.0).to_oklch();
+    /// assert_relative_eq!(rotated_l, l, epsilon = 1e-6);
+    /// assert_relative_eq!(rotated_c, c, epsilon = 1e-6);
+    /// ```
+    pub fn to_oklch(&self) -> (f64, f64, f64) {
+        let (l, a, b) = self.to_oklab();
+        let c = a.hypot(b);
+        let h = if c < 1e-6f64 {
+            0f64
+        } else {
+            b.atan2(a).to_degrees().rem_euclid(360f64)
+        };
+        (l, c, h)
+    }
+
+    /// Creates a solid colour from OKLCH with the hue `h` in degrees,
+    /// the inverse of `.to_oklch()`.
+    ///
+    /// The result is not clamped, OKLCH can describe colours outside of
+    /// the sRGB gamut so use `.clamped()` if necessary.
+    pub fn from_oklch(l: f64, c: f64, h: f64) -> Self {
+        let h = h.to_radians();
+        Self::from_oklab(l, c * h.cos(), c * h.sin())
+    }
+
+    /// Caps the OKLCH chroma at `max_chroma`, keeping the lightness,
+    /// hue and alpha. Colours that are already less vivid are returned
+    /// as they are. This mutes colours more naturally than clamping the
+    /// saturation in HSL.
+    ///
+    /// # Example
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::Colour;
+    ///
+    /// let muted = Colour::red(1.0).clamp_chroma(0.1);
+    /// assert_relative_eq!(muted.to_oklch().1, 0.1, epsilon = 1e-6);
+    /// ```
+    pub fn clamp_chroma(&self, max_chroma: f64) -> Self {
+        let (l, c, h) = self.to_oklch();
+        if c <= max_chroma {
+            return *self;
+        }
+        Self::from_oklch(l, max_chroma.max(0f64), h).with_alpha(self.a)
+    }
+
+    /// Parses the CSS Color 4 `oklch(L C H)` and `oklab(L a b)`
+    /// functional notations, optionally followed by `/ alpha`, into a
+    /// colour through OKLab.
+    ///
+    /// `L` is a number or a percentage of `1`, `C`, `a` and `b` are
+    /// numbers or percentages of `0.4`, `H` is in degrees unless it has
+    /// one of the `deg`, `grad`, `rad` or `turn` units, and alpha is a
+    /// number or a percentage of `1`. Any of them can be `none`. Colours
+    /// outside of the sRGB gamut are clamped.
+    ///
+    /// # Example
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::Colour;
+    ///
+    /// let red = Colour::from_oklch_css("oklch(62.8% 0.2577 29.23)").unwrap();
+    /// assert_relative_eq!(red, Colour::red(1.0), epsilon = 1e-3);
+    ///
+    /// let translucent = Colour::from_oklch_css("oklab(1 0 0 / 50%)").unwrap();
+    /// assert_relative_eq!(translucent, Colour::grey(1.0).with_alpha(0.5), epsilon = 1e-6);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn from_oklch_css(css: &str) -> Result<Self, String> {
+        let css = css.trim();
+        let (name, body) = css
+            .strip_suffix(')')
+            .and_then(|css| css.split_once('('))
+            .ok_or_else(|| format!("`{css}` is not a CSS colour function."))?;
+        let (channels, alpha) = match body.split_once('/') {
+            Some((channels, alpha)) => (channels, css_number(alpha.trim(), 1f64)?),
+            None => (body, 1f64),
+        };
+        let channels: Vec<&str> = channels.split_whitespace().collect();
+        let [l, x, y] = channels[..] else {
+            return Err(format!("Expected 3 channels but found {}.", channels.len()));
+        };
+        let l = css_number(l, 1f64)?.clamp(0f64, 1f64);
+        let (a, b) = match name.trim().to_ascii_lowercase().as_str() {
+            "oklch" => {
+                let (c, h) = (css_number(x, 0.4f64)?.max(0f64), css_hue(y)?.to_radians());
+                (c * h.cos(), c * h.sin())
+            }
+            "oklab" => (css_number(x, 0.4f64)?, css_number(y, 0.4f64)?),
+            name => return Err(format!("Unsupported CSS colour function `{name}`.")),
+        };
+        Ok(Self::from_oklab(l, a, b)
+            .clamped()
+            .with_alpha(alpha.clamp(0f64, 1f64)))
+    }
+
+    /// Parses a CSS hex colour, `#rgb`, `#rgba`, `#rrggbb` or
+    /// `#rrggbbaa`, ignoring case and surrounding whitespace. Alpha is
+    /// `1` when it isn't given.
+    ///
+    /// # Example
+    /// ```
+    /// use tcolour::Colour;
+    ///
+    /// assert_eq!(Colour::from_hex("#ff8000").unwrap(), Colour::from_u8(255, 128, 0));
+    /// assert_eq!(Colour::from_hex("#F80").unwrap(), Colour::from_u8(255, 136, 0));
+    /// assert_eq!(Colour::from_hex("#ff800080").unwrap(), Colour::from_u8_rgba(255, 128, 0, 128));
+    /// assert!(Colour::from_hex("#ff80").is_ok());
+    /// assert!(Colour::from_hex("ff8000").is_err());
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn from_hex(hex: &str) -> Result<Self, String> {
+        let hex = hex.trim();
+        let digits = hex
+            .strip_prefix('#')
+            .ok_or_else(|| format!("`{hex}` is not a hex colour, it must start with `#`."))?;
+        if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(format!("`{hex}` has characters that aren't hex digits."));
+        }
+        let digit = |i: usize| u8::from_str_radix(&digits[i..=i], 16).unwrap();
+        let pair = |i: usize| u8::from_str_radix(&digits[2 * i..2 * i + 2], 16).unwrap();
+        let [r, g, b, a] = match digits.len() {
+            3 | 4 => [0, 1, 2, 3].map(|i| if i < digits.len() { digit(i) * 17 } else { 255 }),
+            6 | 8 => [0, 1, 2, 3].map(|i| if 2 * i < digits.len() { pair(i) } else { 255 }),
+            len => {
+                return Err(format!(
+                    "`{hex}` has {len} hex digits, expected 3, 4, 6 or 8."
+                ));
+            }
+        };
+        Ok(Self::from_u8_rgba(r, g, b, a))
+    }
+
+    /// Parses a CSS colour: a hex colour like `.from_hex()`, a named
+    /// colour like `.from_name()`, the `rgb(r, g, b)` and
+    /// `rgba(r, g, b, a)` functional notations or the `oklch()` and
+    /// `oklab()` notations of `.from_oklch_css()`. Surrounding
+    /// whitespace is ignored.
+    ///
+    /// In `rgb()` and `rgba()` the channels are integers between
+    /// `[0, 255]` and alpha is a number between `[0, 1]` or a
+    /// percentage, either function takes an optional alpha.
+    ///
+    /// # Example
+    /// ```
+    /// use tcolour::Colour;
+    ///
+    /// let orange = Colour::from_u8(255, 128, 0);
+    /// assert_eq!(Colour::from_css("rgb(255, 128, 0)").unwrap(), orange);
+    /// assert_eq!(Colour::from_css("rgba(255,128,0,0.5)").unwrap(), orange.with_alpha(0.5));
+    /// assert_eq!(Colour::from_css("#ff8000").unwrap(), orange);
+    /// assert_eq!(Colour::from_css("rebeccapurple").unwrap(), Colour::from_u8(102, 51, 153));
+    /// assert!(Colour::from_css("rgb(256, 0, 0)").is_err());
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn from_css(css: &str) -> Result<Self, String> {
+        let css = css.trim();
+        if css.starts_with('#') {
+            return Self::from_hex(css);
+        }
+        let Some((name, body)) = css.strip_suffix(')').and_then(|css| css.split_once('(')) else {
+            return Self::from_name(css).ok_or_else(|| {
+                format!("`{css}` is not a CSS colour name, hex colour or function.")
+            });
+        };
+        match name.trim().to_ascii_lowercase().as_str() {
+            "rgb" | "rgba" => {}
+            "oklch" | "oklab" => return Self::from_oklch_css(css),
+            name => return Err(format!("Unsupported CSS colour function `{name}`.")),
+        }
+        let channels: Vec<&str> = body.split(',').map(str::trim).collect();
+        let (rgb, alpha) = match channels[..] {
+            [r, g, b] => ([r, g, b], 1f64),
+            [r, g, b, a] => ([r, g, b], css_number(a, 1f64)?.clamp(0f64, 1f64)),
+            _ => {
+                return Err(format!(
+                    "Expected 3 or 4 comma separated channels but found {}.",
+                    channels.len()
+                ));
+            }
+        };
+        let mut levels = [0u8; 3];
+        for (level, channel) in levels.iter_mut().zip(rgb) {
+            *level = channel
+                .parse()
+                .map_err(|_| format!("`{channel}` is not an integer between 0 and 255."))?;
+        }
+        let [r, g, b] = levels;
+        Ok(Self::from_u8(r, g, b).with_alpha(alpha))
+    }
+
+    /// Interpolates between two colours in OKLCH, taking the
+    /// shortest way around the hue circle. Lightness, chroma and
+    /// alpha are interpolated linearly. If either colour is achromatic,
+    /// the hue of the other is used so that greys don't pull the hue
+    /// around.
+    ///
+    /// The result is not clamped, vivid mixes can land slightly
+    /// outside of the sRGB gamut so use `.clamped()` if necessary.
+    ///
+    /// # Example
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::Colour;
+    ///
+    /// let red = Colour::red(1.0);
+    /// let blue = Colour::blue(1.0);
+    ///
+    /// assert_relative_eq!(red.mix_oklch(blue, 0.0), red, epsilon = 1e-6);
+    /// assert_relative_eq!(red.mix_oklch(blue, 1.0), blue, epsilon = 1e-6);
+    /// ```
+    pub fn mix_oklch(&self, other: Self, t: f64) -> Self {
+        let (l_from, a_from, b_from) = self.to_oklab();
+        let (l_to, a_to, b_to) = other.to_oklab();
+        let (c_from, c_to) = (a_from.hypot(b_from), a_to.hypot(b_to));
+        let (mut h_from, mut h_to) = (b_from.atan2(a_from), b_to.atan2(a_to));
+        if c_from < 1e-6f64 {
+            h_from = h_to;
+        } else if c_to < 1e-6f64 {
+            h_to = h_from;
+        }
+        let tau = core::f64::consts::TAU;
+        let hue_delta = (h_to - h_from + tau / 2f64).rem_euclid(tau) - tau / 2f64;
+
+        let (l, c, h) = (
+            l_from + (l_to - l_from) * t,
+            c_from + (c_to - c_from) * t,
+            h_from + hue_delta * t,
+        );
+        Self::from_oklab(l, c * h.cos(), c * h.sin()).with_alpha(self.a + (other.a - self.a) * t)
+    }
+
+    /// Interpolates between two colours in `space`, returning sRGB.
+    /// Alpha is interpolated linearly in every space. For `MixSpace::Hsl`
+    /// hue takes the shorter way around and, like `.mix_oklch()`, an
+    /// achromatic colour takes the hue of the other.
+    ///
+    /// The result is not clamped.
+    ///
+    /// # Example
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::{Colour, MixSpace};
+    ///
+    /// let (red, green) = (Colour::red(1.0), Colour::green(1.0));
+    ///
+    /// assert_relative_eq!(red.mix(&green, 0.5, MixSpace::Rgb), Colour::solid(0.5, 0.5, 0.0));
+    /// assert_relative_eq!(red.mix(&green, 0.5, MixSpace::Hsl), Colour::solid(1.0, 1.0, 0.0));
+    /// ```
+    pub fn mix(&self, other: &Colour, t: f64, space: MixSpace) -> Self {
+        let alpha = self.a + (other.a - self.a) * t;
+        let lerp = |from: f64, to: f64| from + (to - from) * t;
+        match space {
+            MixSpace::Rgb => self.lerp(*other, t),
+            MixSpace::LinearRgb => self.to_linear().lerp(other.to_linear(), t).to_srgb(),
+            MixSpace::Hsl => {
+                let (mut h_from, s_from, l_from) = self.to_hsl();
+                let (mut h_to, s_to, l_to) = other.to_hsl();
+                if s_from < 1e-6f64 {
+                    h_from = h_to;
+                } else if s_to < 1e-6f64 {
+                    h_to = h_from;
+                }
+                let hue_delta = (h_to - h_from + 180f64).rem_euclid(360f64) - 180f64;
+                Self::from_hsl(
+                    h_from + hue_delta * t,
+                    lerp(s_from, s_to),
+                    lerp(l_from, l_to),
+                )
+            }
+            MixSpace::Oklab => {
+                let (l_from, a_from, b_from) = self.to_oklab();
+                let (l_to, a_to, b_to) = other.to_oklab();
+                Self::from_oklab(lerp(l_from, l_to), lerp(a_from, a_to), lerp(b_from, b_to))
+            }
+        }
+        .with_alpha(alpha)
+    }
+
+    /// Interpolates between two colours in HSL, returning sRGB. This is
+    /// `.mix()` in `MixSpace::Hsl`: saturation, lightness and alpha are
+    /// interpolated linearly and hue takes the shorter arc, so hue
+    /// transitions stay saturated rather than passing through grey.
+    /// Opposite hues, such as red and cyan, are 180° apart either way
+    /// and go down through the hues, red to cyan by magenta and blue.
+    /// An achromatic colour takes the hue of the other.
+    ///
+    /// # Example
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::Colour;
+    ///
+    /// let (red, cyan) = (Colour::red(1.0), Colour::solid(0.0, 1.0, 1.0));
+    ///
+    /// assert_relative_eq!(red.lerp(cyan, 0.5), Colour::grey(0.5));
+    /// assert_relative_eq!(red.lerp_hsl(cyan, 0.5), Colour::solid(0.5, 0.0, 1.0));
+    /// ```
+    pub fn lerp_hsl(&self, other: Self, t: f64) -> Self {
+        self.mix(&other, t, MixSpace::Hsl)
+    }
+
+    /// Converts to CIELAB under D65 as `(L, a, b)`, ignoring alpha
+    #[allow(clippy::wrong_self_convention)]
+    fn to_lab(&self) -> (f64, f64, f64) {
+        let (r, g, b) = (
+            srgb_to_linear(self.r),
+            srgb_to_linear(self.g),
+            srgb_to_linear(self.b),
+        );
+        // Relative to the D65 white, which is the rows' sums
+        let [x, y, z] = HSLUV_M_INV.map(|[m1, m2, m3]| (m1 * r + m2 * g + m3 * b) / (m1 + m2 + m3));
+        let f = |t: f64| {
+            if t > HSLUV_EPSILON {
+                t.cbrt()
+            } else {
+                (HSLUV_KAPPA * t + 16f64) / 116f64
+            }
+        };
+        let (fx, fy, fz) = (f(x), f(y), f(z));
+        (116f64 * fy - 16f64, 500f64 * (fx - fy), 200f64 * (fy - fz))
+    }
+
+    /// The [CIEDE2000](https://en.wikipedia.org/wiki/Color_difference#CIEDE2000)
+    /// difference between two colours through CIELAB under D65, ignoring
+    /// alpha. Unlike the euclidean distance between RGB values this
+    /// follows how different the colours look, around `1` being just
+    /// noticeable.
+    ///
+    /// # Example
+    /// ```
+    /// use tcolour::Colour;
+    ///
+    /// let palette = [Colour::red(1.0), Colour::green(1.0), Colour::blue(1.0)];
+    /// let pixel = Colour::solid(0.3, 0.2, 0.7);
+    /// let nearest = palette
+    ///     .iter()
+    ///     .min_by(|a, b| pixel.delta_e(a).total_cmp(&pixel.delta_e(b)));
+    ///
+    /// assert_eq!(nearest, Some(&Colour::blue(1.0)));
+    /// ```
+    pub fn delta_e(&self, other: &Colour) -> f64 {
+        ciede2000(self.to_lab(), other.to_lab())
+    }
+
+    /// Converts to CIELUV's cylindrical form as `(L, C, h)` with
+    /// `h` in degrees, ignoring alpha.
+    #[allow(clippy::wrong_self_convention)]
+    fn to_lchuv(&self) -> (f64, f64, f64) {
+        let (r, g, b) = (
+            srgb_to_linear(self.r),
+            srgb_to_linear(self.g),
+            srgb_to_linear(self.b),
+        );
+        let [x, y, z] = HSLUV_M_INV.map(|[m1, m2, m3]| m1 * r + m2 * g + m3 * b);
+        let l = if y <= HSLUV_EPSILON {
+            y * HSLUV_KAPPA
+        } else {
+            116f64 * y.cbrt() - 16f64
+        };
+        if l == 0f64 {
+            return (0f64, 0f64, 0f64);
+        }
+        let divider = x + 15f64 * y + 3f64 * z;
+        let u = 13f64 * l * (4f64 * x / divider - HSLUV_REF_U);
+        let v = 13f64 * l * (9f64 * y / divider - HSLUV_REF_V);
+        let c = u.hypot(v);
+        let h = if c < 1e-8f64 {
+            0f64
+        } else {
+            v.atan2(u).to_degrees().rem_euclid(360f64)
+        };
+        (l, c, h)
+    }
+
+    /// Creates a solid colour from CIELUV's cylindrical form `(L, C, h)`
+    /// with `h` in degrees.
+    fn from_lchuv(l: f64, c: f64, h: f64) -> Self {
+        if l == 0f64 {
+            return Self::grey(0f64);
+        }
+        let h = h.to_radians();
+        let (u, v) = (c * h.cos(), c * h.sin());
+        let var_u = u / (13f64 * l) + HSLUV_REF_U;
+        let var_v = v / (13f64 * l) + HSLUV_REF_V;
+        let y = if l <= 8f64 {
+            l / HSLUV_KAPPA
+        } else {
+            ((l + 16f64) / 116f64).powi(3)
+        };
+        let x = -(9f64 * y * var_u) / ((var_u - 4f64) * var_v - var_u * var_v);
+        let z = (9f64 * y - 15f64 * var_v * y - var_v * x) / (3f64 * var_v);
+        let [r, g, b] = HSLUV_M.map(|[m1, m2, m3]| linear_to_srgb(m1 * x + m2 * y + m3 * z));
+        Self::solid(r, g, b)
+    }
+
+    /// Converts to [HSL](https://en.wikipedia.org/wiki/HSL_and_HSV) as
+    /// `(h, s, l)`, with the hue in degrees within `[0, 360)` and the
+    /// saturation and lightness between `[0, 1]`, ignoring alpha. Greys
+    /// have a hue and saturation of `0`.
+    ///
+    /// # Example
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::Colour;
+    ///
+    /// let (h, s, l) = Colour::solid(0.25, 0.75, 0.75).to_hsl();
+    /// assert_relative_eq!(h, 180.0);
+    /// assert_relative_eq!(s, 0.5);
+    /// assert_relative_eq!(l, 0.5);
+    /// ```
+    pub fn to_hsl(&self) -> (f64, f64, f64) {
+        let max = self.r.max(self.g.max(self.b));
+        let min = self.r.min(self.g.min(self.b));
+        let (chroma, l) = (max - min, (max + min) / 2f64);
+        if chroma <= 0f64 {
+            return (0f64, 0f64, l);
+        }
+        let h = if max == self.r {
+            (self.g - self.b) / chroma
+        } else if max == self.g {
+            (self.b - self.r) / chroma + 2f64
+        } else {
+            (self.r - self.g) / chroma + 4f64
+        };
+        let s = chroma / (1f64 - (2f64 * l - 1f64).abs());
+        ((h * 60f64).rem_euclid(360f64), s, l)
+    }
+
+    /// Creates a solid colour from [HSL](https://en.wikipedia.org/wiki/HSL_and_HSV),
+    /// the inverse of `.to_hsl()`. The hue `h` is in degrees and wraps
+    /// around, the saturation `s` and lightness `l` are between `[0, 1]`.
+    pub fn from_hsl(h: f64, s: f64, l: f64) -> Self {
+        let chroma = (1f64 - (2f64 * l - 1f64).abs()) * s;
+        let channel = |n: f64| {
+            let k = (n + h.rem_euclid(360f64) / 30f64).rem_euclid(12f64);
+            l - chroma / 2f64 * (k - 3f64).min(9f64 - k).clamp(-1f64, 1f64)
+        };
+        Self::solid(channel(0f64), channel(8f64), channel(4f64))
+    }
+
+    /// Rotates the HSL hue by `degrees`, wrapping around the hue circle
+    /// and keeping the saturation, lightness and alpha. Greys have no
+    /// hue so they are unchanged.
+    ///
+    /// # Example
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::Colour;
+    ///
+    /// // The complementary colour of red
+    /// assert_relative_eq!(Colour::red(1.0).rotate_hue(180.0), Colour::solid(0.0, 1.0, 1.0));
+    /// ```
+    pub fn rotate_hue(&self, degrees: f64) -> Self {
+        let (h, s, l) = self.to_hsl();
+        Self::from_hsl(h + degrees, s, l).with_alpha(self.a)
+    }
+
+    /// Sets the HSL hue to `hue` degrees, keeping the saturation,
+    /// lightness and alpha. Greys have no saturation so they stay grey.
+    ///
+    /// # Example
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::Colour;
+    ///
+    /// assert_relative_eq!(Colour::red(1.0).with_hue(120.0), Colour::green(1.0));
+    /// ```
+    pub fn with_hue(self, hue: f64) -> Self {
+        let (_, s, l) = self.to_hsl();
+        Self::from_hsl(hue, s, l).with_alpha(self.a)
+    }
+
+    /// Sets the HSL saturation to `saturation`, keeping the hue,
+    /// lightness and alpha. A saturation of `0` gives a grey.
+    pub fn with_saturation(self, saturation: f64) -> Self {
+        let (h, _, l) = self.to_hsl();
+        Self::from_hsl(h, saturation, l).with_alpha(self.a)
+    }
+
+    /// Sets the HSL lightness to `lightness`, keeping the hue,
+    /// saturation and alpha.
+    ///
+    /// # Example
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::Colour;
+    ///
+    /// // Normalising the brightness of a palette
+    /// let palette = [Colour::solid(0.2, 0.1, 0.1), Colour::solid(0.4, 0.9, 0.4)];
+    /// for colour in palette.map(|colour| colour.with_lightness(0.5)) {
+    ///     assert_relative_eq!(colour.to_hsl().2, 0.5);
+    /// }
+    /// ```
+    pub fn with_lightness(self, lightness: f64) -> Self {
+        let (h, s, _) = self.to_hsl();
+        Self::from_hsl(h, s, lightness).with_alpha(self.a)
+    }
+
+    /// Raises the HSL lightness by `amount`, clamped between `[0, 1]`,
+    /// keeping the hue, saturation and alpha. Unlike scaling the
+    /// channels this doesn't shift the hue.
+    ///
+    /// # Example
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::Colour;
+    ///
+    /// let base = Colour::solid(0.2, 0.4, 0.8);
+    /// let hover = base.lightened(0.1);
+    /// assert_relative_eq!(hover, Colour::solid(0.36, 0.52, 0.84));
+    /// ```
+    pub fn lightened(&self, amount: f64) -> Self {
+        let (h, s, l) = self.to_hsl();
+        Self::from_hsl(h, s, (l + amount).clamp(0f64, 1f64)).with_alpha(self.a)
+    }
+
+    /// Lowers the HSL lightness by `amount`, see `.lightened()`.
+    pub fn darkened(&self, amount: f64) -> Self {
+        self.lightened(-amount)
+    }
+
+    /// Creates a solid colour from [HSLuv](https://www.hsluv.org/), with
+    /// the hue `h` in degrees and the saturation `s` and lightness `l`
+    /// between `[0, 100]` as in the reference implementation.
+    ///
+    /// Unlike HSL, equal lightness values look equally bright across
+    /// every hue. Every saturation is in gamut, at `s = 100` the colour
+    /// is as vivid as sRGB allows for that hue and lightness.
+    ///
+    /// # Example
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::Colour;
+    ///
+    /// let red = Colour::from_hsluv(12.177, 100.0, 53.237);
+    /// assert_relative_eq!(red, Colour::red(1.0), epsilon = 1e-4);
+    /// ```
+    pub fn from_hsluv(h: f64, s: f64, l: f64) -> Self {
+        if l > 99.9999999f64 {
+            return Self::from_lchuv(100f64, 0f64, h);
+        }
+        if l < 1e-8f64 {
+            return Self::grey(0f64);
+        }
+        Self::from_lchuv(l, hsluv_max_chroma(l, h) / 100f64 * s, h)
+    }
+
+    /// Converts to [HSLuv](https://www.hsluv.org/) as `(h, s, l)`, with
+    /// the hue in degrees and the saturation and lightness between
+    /// `[0, 100]`, ignoring alpha.
+    pub fn to_hsluv(&self) -> (f64, f64, f64) {
+        let (l, c, h) = self.to_lchuv();
+        if l > 99.9999999f64 {
+            return (h, 0f64, 100f64);
+        }
+        if l < 1e-8f64 {
+            return (h, 0f64, 0f64);
+        }
+        (h, c / hsluv_max_chroma(l, h) * 100f64, l)
+    }
+
+    /// Creates a solid colour from [HPLuv](https://www.hsluv.org/), the
+    /// pastel variant of HSLuv, with the hue `h` in degrees and the
+    /// saturation `s` and lightness `l` between `[0, 100]`.
+    ///
+    /// HPLuv scales saturation by the chroma that is in gamut for every
+    /// hue at that lightness, so only `s <= 100` is in gamut but a given
+    /// saturation has the same chroma regardless of hue.
+    pub fn from_hpluv(h: f64, s: f64, l: f64) -> Self {
+        if l > 99.9999999f64 {
+            return Self::from_lchuv(100f64, 0f64, h);
+        }
+        if l < 1e-8f64 {
+            return Self::grey(0f64);
+        }
+        Self::from_lchuv(l, hpluv_max_chroma(l) / 100f64 * s, h)
+    }
+
+    /// Converts to [HPLuv](https://www.hsluv.org/) as `(h, s, l)`, with
+    /// the hue in degrees and the lightness between `[0, 100]`, ignoring
+    /// alpha. Vivid colours have a saturation above `100`.
+    pub fn to_hpluv(&self) -> (f64, f64, f64) {
+        let (l, c, h) = self.to_lchuv();
+        if l > 99.9999999f64 {
+            return (h, 0f64, 100f64);
+        }
+        if l < 1e-8f64 {
+            return (h, 0f64, 0f64);
+        }
+        (h, c / hpluv_max_chroma(l) * 100f64, l)
+    }
+
+    /// Converts to CMYK as `(c, m, y, k)` with the naive device
+    /// conversion `k = 1 - max(r, g, b)`. Black has no ink other than
+    /// `k`. CMYK has no alpha so it is dropped, use `.to_cmyka()` to
+    /// keep it through a round trip.
+    ///
+    /// This isn't colour managed, no ICC profile or ink limit is taken
+    /// into account, so it is only a rough preview of print.
+    ///
+    /// # Example
+    /// ```
+    /// use tcolour::Colour;
+    ///
+    /// assert_eq!(Colour::red(1.0).to_cmyk(), (0.0, 1.0, 1.0, 0.0));
+    /// assert_eq!(Colour::grey(0.0).to_cmyk(), (0.0, 0.0, 0.0, 1.0));
+    /// ```
+    pub fn to_cmyk(&self) -> (f64, f64, f64, f64) {
+        let max = self.r.max(self.g.max(self.b));
+        if max <= 0f64 {
+            return (0f64, 0f64, 0f64, 1f64);
+        }
+        (
+            (max - self.r) / max,
+            (max - self.g) / max,
+            (max - self.b) / max,
+            1f64 - max,
+        )
+    }
+
+    /// Creates a solid colour from CMYK with the naive device
+    /// conversion, the inverse of `.to_cmyk()`. The alpha is always
+    /// `1`, see `.from_cmyka()`.
+    pub fn from_cmyk(c: f64, m: f64, y: f64, k: f64) -> Self {
+        Self::solid(
+            (1f64 - c) * (1f64 - k),
+            (1f64 - m) * (1f64 - k),
+            (1f64 - y) * (1f64 - k),
+        )
+    }
+
+    /// Converts to CMYK like `.to_cmyk()` with alpha carried along as
+    /// `(c, m, y, k, a)`.
+    ///
+    /// # Example
+    /// ```
+    /// use tcolour::Colour;
+    ///
+    /// let translucent = Colour::red(1.0).with_alpha(0.5);
+    /// let (c, m, y, k, a) = translucent.to_cmyka();
+    ///
+    /// assert_eq!((c, m, y, k, a), (0.0, 1.0, 1.0, 0.0, 0.5));
+    /// assert_eq!(Colour::from_cmyka(c, m, y, k, a), translucent);
+    /// ```
+    pub fn to_cmyka(&self) -> (f64, f64, f64, f64, f64) {
+        let (c, m, y, k) = self.to_cmyk();
+        (c, m, y, k, self.a)
+    }
+
+    /// Creates a colour from CMYK and alpha, the inverse of `.to_cmyka()`.
+    pub fn from_cmyka(c: f64, m: f64, y: f64, k: f64, a: f64) -> Self {
+        Self::from_cmyk(c, m, y, k).with_alpha(a)
+    }
+
+    /// Approximates the colour of a blackbody at `kelvin` with Tanner
+    /// Helland's fit, for lighting. The temperature is clamped between
+    /// `[1000, 40000]` K, around 6500 K is white with warmer oranges
+    /// below and cooler blues above.
+    ///
+    /// # Example
+    /// ```
+    /// use tcolour::Colour;
+    ///
+    /// let candle = Colour::from_kelvin(1900.0);
+    /// let daylight = Colour::from_kelvin(6500.0);
+    /// let sky = Colour::from_kelvin(10000.0);
+    ///
+    /// assert!(candle.r > candle.g && candle.g > candle.b);
+    /// assert!(daylight.all(|v| v > 0.97));
+    /// assert!(sky.b > sky.r);
+    /// ```
+    pub fn from_kelvin(kelvin: f64) -> Self {
+        let t = kelvin.clamp(1000f64, 40000f64) / 100f64;
+        let (r, g, b) = if t <= 66f64 {
+            (
+                255f64,
+                99.4708025861f64 * t.ln() - 161.1195681661f64,
+                if t <= 19f64 {
+                    0f64
+                } else {
+                    138.5177312231f64 * (t - 10f64).ln() - 305.0447927307f64
+                },
+            )
+        } else {
+            (
+                329.698727446f64 * (t - 60f64).powf(-0.1332047592f64),
+                288.1221695283f64 * (t - 60f64).powf(-0.0755148492f64),
+                255f64,
+            )
+        };
+        Self::solid(r, g, b).map(|v| v.clamp(0f64, 255f64) / 255f64)
+    }
+
+    /// Looks the colour up in a 3D LUT, trilinearly interpolating
+    /// between the eight surrounding grid points. The channels are
+    /// clamped to the LUT's domain first and alpha is kept as is.
+    ///
+    /// # Example
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::{Colour, Lut3d};
+    ///
+    /// let lut = Lut3d::from_fn(9, |r, g, b| Colour::grey((r + g + b) / 3.0)).unwrap();
+    /// let colour = Colour::new(0.3, 0.6, 0.9, 0.5);
+    /// assert_relative_eq!(colour.apply_lut3d(&lut), Colour::grey(0.6).with_alpha(0.5));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn apply_lut3d(&self, lut: &Lut3d) -> Self {
+        let (domain_min, domain_max) = lut.domain();
+        let step = (lut.size() - 1) as f64;
+        // The lower grid point and the position between it and the next
+        let cell = |v: f64, i: usize| {
+            let x =
+                ((v - domain_min[i]) / (domain_max[i] - domain_min[i])).clamp(0f64, 1f64) * step;
+            let cell = (x.floor() as usize).min(lut.size() - 2);
+            (cell, x - cell as f64)
+        };
+        let ((r, r_t), (g, g_t), (b, b_t)) = (cell(self.r, 0), cell(self.g, 1), cell(self.b, 2));
+        let lerp = |from: Colour, to: Colour, t: f64| from + (to - from) * t;
+        let plane = |b: usize| {
+            lerp(
+                lerp(lut.get(r, g, b), lut.get(r + 1, g, b), r_t),
+                lerp(lut.get(r, g + 1, b), lut.get(r + 1, g + 1, b), r_t),
+                g_t,
+            )
+        };
+        lerp(plane(b), plane(b + 1), b_t).with_alpha(self.a)
+    }
+}
+
+impl From<[f64; 3]> for Colour {
+    fn from(value: [f64; 3]) -> Self {
+        Colour::solid(value[0], value[1], value[2])
+    }
+}
+impl From<[f64; 4]> for Colour {
+    fn from(value: [f64; 4]) -> Self {
+        Colour::new(value[0], value[1], value[2], value[3])
+    }
+}
+impl From<(f64, f64, f64)> for Colour {
+    fn from((r, g, b): (f64, f64, f64)) -> Self {
+        Colour::solid(r, g, b)
+    }
+}
+impl From<(f64, f64, f64, f64)> for Colour {
+    fn from((r, g, b, a): (f64, f64, f64, f64)) -> Self {
+        Colour::new(r, g, b, a)
+    }
+}
+
+impl From<[u8; 3]> for Colour {
+    fn from(value: [u8; 3]) -> Self {
         Colour::from_u8(value[0], value[1], value[2])
     }
-}
-impl From<[u8; 4]> for Colour {
-    fn from(value: [u8; 4]) -> Self {
-        Colour::from_u8_rgba(value[0], value[1], value[2], value[3])
+}
+impl From<[u8; 4]> for Colour {
+    fn from(value: [u8; 4]) -> Self {
+        Colour::from_u8_rgba(value[0], value[1], value[2], value[3])
+    }
+}
+impl From<(u8, u8, u8)> for Colour {
+    fn from((r, g, b): (u8, u8, u8)) -> Self {
+        Colour::from_u8(r, g, b)
+    }
+}
+impl From<(u8, u8, u8, u8)> for Colour {
+    fn from((r, g, b, a): (u8, u8, u8, u8)) -> Self {
+        Colour::from_u8_rgba(r, g, b, a)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TryFrom<&[f64]> for Colour {
+    type Error = String;
+    fn try_from(value: &[f64]) -> Result<Self, Self::Error> {
+        if value.len() > 4 {
+            Err("There are too many elements.".to_string())
+        } else if value.len() < 3 {
+            Err("There are not enough elements.".to_string())
+        } else if value.len() == 3 {
+            Ok(Colour::solid(value[0], value[1], value[2]))
+        } else {
+            Ok(Colour::new(value[0], value[1], value[2], value[3]))
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TryFrom<Vec<f64>> for Colour {
+    type Error = String;
+    fn try_from(value: Vec<f64>) -> Result<Self, Self::Error> {
+        if value.len() > 4 {
+            Err("There are too many elements.".to_string())
+        } else if value.len() < 3 {
+            Err("There are not enough elements.".to_string())
+        } else if value.len() == 3 {
+            Ok(Colour::solid(value[0], value[1], value[2]))
+        } else {
+            Ok(Colour::new(value[0], value[1], value[2], value[3]))
+        }
+    }
+}
+
+#[allow(clippy::from_over_into)]
+impl Into<[f64; 4]> for Colour {
+    fn into(self) -> [f64; 4] {
+        [self.r, self.g, self.b, self.a]
+    }
+}
+#[allow(clippy::from_over_into)]
+impl Into<(f64, f64, f64, f64)> for Colour {
+    fn into(self) -> (f64, f64, f64, f64) {
+        (self.r, self.g, self.b, self.a)
+    }
+}
+#[cfg(feature = "alloc")]
+#[allow(clippy::from_over_into)]
+impl Into<Vec<f64>> for Colour {
+    fn into(self) -> Vec<f64> {
+        Vec::from([self.r, self.g, self.b, self.a])
+    }
+}
+#[allow(clippy::from_over_into)]
+impl Into<[u8; 4]> for Colour {
+    fn into(self) -> [u8; 4] {
+        let (r, g, b, a) = self.as_u8_rgba();
+        [r, g, b, a]
+    }
+}
+#[allow(clippy::from_over_into)]
+impl Into<(u8, u8, u8, u8)> for Colour {
+    fn into(self) -> (u8, u8, u8, u8) {
+        self.as_u8_rgba()
+    }
+}
+#[cfg(feature = "alloc")]
+#[allow(clippy::from_over_into)]
+impl Into<Vec<u8>> for Colour {
+    fn into(self) -> Vec<u8> {
+        let (r, g, b, a) = self.as_u8_rgba();
+        Vec::from([r, g, b, a])
+    }
+}
+/// Flattens colours into an `[r, g, b, a, r, g, b, a, ...]` buffer
+#[cfg(feature = "alloc")]
+pub fn colours_to_f64(colours: &[Colour]) -> Vec<f64> {
+    colours
+        .iter()
+        .flat_map(|colour| [colour.r, colour.g, colour.b, colour.a])
+        .collect()
+}
+
+/// Reads colours from an `[r, g, b, a, r, g, b, a, ...]` buffer, the
+/// length of which must be a multiple of 4.
+///
+/// # Example
+/// ```
+/// use tcolour::{colours_from_f64, colours_to_f64, Colour, ColourError};
+///
+/// let colours = vec![Colour::red(1.0), Colour::transparent()];
+/// let buffer = colours_to_f64(&colours);
+///
+/// assert_eq!(buffer, vec![1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0]);
+/// assert_eq!(colours_from_f64(&buffer), Ok(colours));
+/// assert_eq!(colours_from_f64(&buffer[1..]), Err(ColourError::InvalidBufferLength(7)));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn colours_from_f64(buffer: &[f64]) -> Result<Vec<Colour>, ColourError> {
+    if buffer.len() % 4 != 0 {
+        return Err(ColourError::InvalidBufferLength(buffer.len()));
+    }
+    Ok(buffer
+        .chunks_exact(4)
+        .map(|chunk| Colour::new(chunk[0], chunk[1], chunk[2], chunk[3]))
+        .collect())
+}
+
+/// Flattens colours into an `[r, g, b, a, r, g, b, a, ...]` buffer of
+/// `u8`s, converting with `.as_u8_rgba()`
+#[cfg(feature = "alloc")]
+pub fn colours_to_u8(colours: &[Colour]) -> Vec<u8> {
+    colours
+        .iter()
+        .flat_map(|&colour| Into::<[u8; 4]>::into(colour))
+        .collect()
+}
+
+/// Reads colours from an `[r, g, b, a, r, g, b, a, ...]` buffer of
+/// `u8`s, the length of which must be a multiple of 4.
+#[cfg(feature = "alloc")]
+pub fn colours_from_u8(buffer: &[u8]) -> Result<Vec<Colour>, ColourError> {
+    if buffer.len() % 4 != 0 {
+        return Err(ColourError::InvalidBufferLength(buffer.len()));
+    }
+    Ok(buffer
+        .chunks_exact(4)
+        .map(|chunk| Colour::from_u8_rgba(chunk[0], chunk[1], chunk[2], chunk[3]))
+        .collect())
+}
+
+#[cfg(feature = "ratatui")]
+impl From<ratatui::style::Color> for Colour {
+    fn from(colour: ratatui::style::Color) -> Colour {
+        match colour {
+            ratatui::style::Color::Black => Colour::from_u8(0, 0, 0),
+            ratatui::style::Color::Red => Colour::from_u8(255, 0, 0),
+            ratatui::style::Color::Green => Colour::from_u8(0, 255, 0),
+            ratatui::style::Color::Yellow => Colour::from_u8(255, 255, 0),
+            ratatui::style::Color::Blue => Colour::from_u8(0, 0, 255),
+            ratatui::style::Color::Magenta => Colour::from_u8(255, 0, 255),
+            ratatui::style::Color::Cyan => Colour::from_u8(0, 255, 255),
+            ratatui::style::Color::Gray => Colour::from_u8(169, 169, 169),
+            ratatui::style::Color::DarkGray => Colour::from_u8(128, 128, 128),
+            ratatui::style::Color::LightRed => Colour::from_u8(255, 128, 128),
+            ratatui::style::Color::LightGreen => Colour::from_u8(128, 255, 128),
+            ratatui::style::Color::LightYellow => Colour::from_u8(255, 255, 128),
+            ratatui::style::Color::LightBlue => Colour::from_u8(128, 128, 255),
+            ratatui::style::Color::LightMagenta => Colour::from_u8(255, 128, 255),
+            ratatui::style::Color::LightCyan => Colour::from_u8(128, 255, 255),
+            ratatui::style::Color::White => Colour::from_u8(255, 255, 255),
+            ratatui::style::Color::Rgb(r, g, b) => Colour::from_u8(r, g, b),
+            ratatui::style::Color::Indexed(index) => Colour::from_xterm256(index),
+            _ => Colour::from_u8(0, 0, 0), // Default case for unknown colors
+        }
+    }
+}
+
+#[cfg(feature = "ratatui")]
+#[allow(clippy::from_over_into)]
+impl Into<ratatui::style::Color> for Colour {
+    fn into(self) -> ratatui::style::Color {
+        let (r, g, b) = self.as_u8();
+        ratatui::style::Color::Rgb(r, g, b)
+    }
+}
+
+#[cfg(feature = "ratatui")]
+#[allow(clippy::from_over_into)]
+impl Into<ratatui::style::Color> for &Colour {
+    fn into(self) -> ratatui::style::Color {
+        let (r, g, b) = self.as_u8();
+        ratatui::style::Color::Rgb(r, g, b)
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl From<nalgebra::Vector4<f64>> for Colour {
+    fn from(value: nalgebra::Vector4<f64>) -> Colour {
+        Colour::new(value.x, value.y, value.z, value.w)
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+#[allow(clippy::from_over_into)]
+impl Into<nalgebra::Vector4<f64>> for Colour {
+    fn into(self) -> nalgebra::Vector4<f64> {
+        nalgebra::Vector4::new(self.r, self.g, self.b, self.a)
+    }
+}
+
+/// With `alpha = 1`
+#[cfg(feature = "nalgebra")]
+impl From<nalgebra::Vector3<f64>> for Colour {
+    fn from(value: nalgebra::Vector3<f64>) -> Colour {
+        Colour::solid(value.x, value.y, value.z)
+    }
+}
+
+/// Drops the alpha channel
+#[cfg(feature = "nalgebra")]
+#[allow(clippy::from_over_into)]
+impl Into<nalgebra::Vector3<f64>> for Colour {
+    fn into(self) -> nalgebra::Vector3<f64> {
+        nalgebra::Vector3::new(self.r, self.g, self.b)
+    }
+}
+
+#[cfg(feature = "glam")]
+impl From<glam::Vec4> for Colour {
+    fn from(value: glam::Vec4) -> Colour {
+        Colour::new(
+            value.x as f64,
+            value.y as f64,
+            value.z as f64,
+            value.w as f64,
+        )
+    }
+}
+
+#[cfg(feature = "glam")]
+#[allow(clippy::from_over_into)]
+impl Into<glam::Vec4> for Colour {
+    fn into(self) -> glam::Vec4 {
+        glam::Vec4::new(self.r as f32, self.g as f32, self.b as f32, self.a as f32)
+    }
+}
+
+/// With `alpha = 1`
+#[cfg(feature = "glam")]
+impl From<glam::Vec3> for Colour {
+    fn from(value: glam::Vec3) -> Colour {
+        Colour::solid(value.x as f64, value.y as f64, value.z as f64)
+    }
+}
+
+/// Drops the alpha channel
+#[cfg(feature = "glam")]
+#[allow(clippy::from_over_into)]
+impl Into<glam::Vec3> for Colour {
+    fn into(self) -> glam::Vec3 {
+        glam::Vec3::new(self.r as f32, self.g as f32, self.b as f32)
+    }
+}
+
+#[cfg(feature = "image")]
+impl From<Colour> for image::Rgba<u8> {
+    fn from(colour: Colour) -> Self {
+        image::Rgba(colour.into())
+    }
+}
+
+/// Drops the alpha channel
+#[cfg(feature = "image")]
+impl From<Colour> for image::Rgb<u8> {
+    fn from(colour: Colour) -> Self {
+        let (r, g, b) = colour.as_u8();
+        image::Rgb([r, g, b])
+    }
+}
+
+#[cfg(feature = "image")]
+impl From<image::Rgba<u8>> for Colour {
+    fn from(image::Rgba([r, g, b, a]): image::Rgba<u8>) -> Self {
+        Colour::from_u8_rgba(r, g, b, a)
+    }
+}
+
+/// Solid colours like `Colour::random()`
+#[cfg(feature = "rand")]
+impl rand::distr::Distribution<Colour> for rand::distr::StandardUniform {
+    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Colour {
+        Colour::random(rng)
+    }
+}
+
+/// A `Colour` that is `Hash`, `Eq` and `Ord` by the bit patterns of its
+/// channels, for use as a `HashMap` or `BTreeMap` key. Only exactly
+/// equal colours are equal, so `0.0` and `-0.0` differ while `NaN`s with
+/// the same bits are the same. Ordering is lexicographic over
+/// `[r, g, b, a]` by `f64::total_cmp()`.
+///
+/// # Example
+/// ```
+/// use std::collections::HashSet;
+/// use tcolour::{Colour, OrderedColour};
+///
+/// let palette: HashSet<OrderedColour> = [Colour::red(1.0), Colour::red(1.0), Colour::blue(1.0)]
+///     .into_iter()
+///     .map(OrderedColour)
+///     .collect();
+///
+/// assert_eq!(palette.len(), 2);
+/// ```
+#[cfg(feature = "hash")]
+#[derive(Clone, Copy, Debug)]
+pub struct OrderedColour(pub Colour);
+
+#[cfg(feature = "hash")]
+impl PartialEq for OrderedColour {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_bits() == other.0.to_bits()
+    }
+}
+
+#[cfg(feature = "hash")]
+impl Eq for OrderedColour {}
+
+#[cfg(feature = "hash")]
+impl core::hash::Hash for OrderedColour {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.0.to_bits().hash(state);
+    }
+}
+
+#[cfg(feature = "hash")]
+impl PartialOrd for OrderedColour {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(feature = "hash")]
+impl Ord for OrderedColour {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        let (a, b) = (self.0, other.0);
+        a.r.total_cmp(&b.r)
+            .then(a.g.total_cmp(&b.g))
+            .then(a.b.total_cmp(&b.b))
+            .then(a.a.total_cmp(&b.a))
+    }
+}
+
+#[cfg(feature = "hash")]
+impl From<Colour> for OrderedColour {
+    fn from(colour: Colour) -> Self {
+        Self(colour)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Colour {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq([self.r, self.g, self.b, self.a])
+    }
+}
+
+/// Deserializes from either `[r, g, b]` or `[r, g, b, a]`, with
+/// `alpha = 1` in the former case.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Colour {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ColourVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for ColourVisitor {
+            type Value = Colour;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("an array of 3 or 4 floats")
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<Colour, A::Error> {
+                let mut channels = [1f64; 4];
+                let mut len = 0;
+                while let Some(value) = seq.next_element()? {
+                    if len == 4 {
+                        return Err(serde::de::Error::invalid_length(len + 1, &self));
+                    }
+                    channels[len] = value;
+                    len += 1;
+                }
+                if len < 3 {
+                    return Err(serde::de::Error::invalid_length(len, &self));
+                }
+                Ok(Colour::from(channels))
+            }
+        }
+
+        deserializer.deserialize_seq(ColourVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use approx::assert_relative_eq;
+    use crate::{BlendMode, BlendSpace, Colour, ColourError, MixSpace};
+    
+    #[cfg(feature="image-tests")]
+    use color_eyre::eyre::Result;
+
+    #[test]
+    pub fn ops_test() {
+        let a = Colour::red(1.0);
+        let b = Colour::grey(0.5);
+        assert_relative_eq!(a * b, Colour::red(0.5));
+        assert_relative_eq!(a + b, Colour::solid(1.5, 0.5, 0.5));
+        assert_relative_eq!(a - b, Colour::solid(0.5, -0.5, -0.5));
+        assert_relative_eq!(2f64 * b, Colour::grey(1.0));
+        let c = Colour::grey(1.0);
+        let d = Colour::transparent();
+        assert_relative_eq!(d - c, Colour::grey(-1.0).with_alpha(0.0));
+    }
+
+    #[test]
+    pub fn cleaned_test() {
+        let subnormal = f64::MIN_POSITIVE / 2.0;
+        assert!(subnormal > 0.0 && !f64::is_normal(subnormal));
+        let colour = Colour::new(f64::NAN, f64::INFINITY, subnormal, 0.5);
+
+        assert_eq!(colour.cleaned(), Colour::new(1.0, 1.0, 1.0, 0.5));
+        assert_eq!(colour.cleaned_to(1.0), colour.cleaned());
+        assert_eq!(colour.cleaned_to(0.0), Colour::new(0.0, 0.0, 0.0, 0.5));
+        assert_eq!(colour.sanitised(), Colour::new(1.0, 1.0, 0.0, 0.5));
+        let sanitised = Colour::new(-0.0, f64::NEG_INFINITY, -subnormal, f64::NAN).sanitised();
+        assert_eq!(sanitised, Colour::new(0.0, 1.0, 0.0, 1.0));
+        // Negative subnormals flush to 0 too, and -0 is kept as it is
+        assert!(sanitised.r.is_sign_negative());
+        assert!(sanitised.b.is_sign_positive());
+        assert_eq!(Colour::grey(1e-310).sanitised(), Colour::grey(0.0));
+        assert_eq!(
+            Colour::grey(f64::MIN_POSITIVE).sanitised(),
+            Colour::grey(f64::MIN_POSITIVE)
+        );
+
+        // Valid colours are left alone by all of them
+        let valid = Colour::new(0.0, 0.25, 1.0, 0.75);
+        assert_eq!(valid.cleaned(), valid);
+        assert_eq!(valid.cleaned_to(0.0), valid);
+        assert_eq!(valid.sanitised(), valid);
+        let mut cleaned = colour;
+        cleaned.clean();
+        assert_eq!(cleaned, colour.cleaned());
+    }
+
+    #[test]
+    pub fn blend_normality_test() {
+
+        use strum::IntoEnumIterator;
+
+        for blend_mode in BlendMode::iter() {
+            for _ in 0..5000 {
+                let base = Colour::random_rgba(&mut rand::rng());
+                let blend = Colour::random_rgba(&mut rand::rng());
+                let blended = base.blend(blend, blend_mode);
+
+                // We expect these to lie outside of [0,1] and whether these are normalised or clamped
+                // is up to the user
+                if [
+                    BlendMode::Addition,
+                    BlendMode::Divide,
+                    BlendMode::Subtract,
+                    BlendMode::LinearBurn,
+                    BlendMode::LinearLight,
+                ]
+                .contains(&blend_mode)
+                {
+                    assert!(
+                        blended.cleaned() == blended,
+                        "{:?}: ({:?} blends onto {:?}) = {:?}",
+                        blend_mode,
+                        blend,
+                        base,
+                        blended
+                    )
+                } else {
+                    assert!(
+                        blended.is_normal(),
+                        "{:?}: ({:?} blends onto {:?}) = {:?}",
+                        blend_mode,
+                        blend,
+                        base,
+                        blended
+                    )
+                }
+            }
+        }
+    }
+
+    #[cfg(feature="image-tests")]
+    #[test]
+    pub fn blend_visual_test() -> Result<()> {
+        use image::{DynamicImage, GenericImageView, ImageFormat, ImageReader, Rgba, RgbaImage};
+
+        let cat = ImageReader::open("test/cat.jpg")?.decode()?;
+        let bricks = ImageReader::open("test/bricks.png")?.decode()?;
+        let get_colours = |image: &DynamicImage| -> Vec<Colour> {
+            image
+                .pixels()
+                .map(|(_, _, pixel)| {
+                    Colour::from_u8_rgba(pixel.0[0], pixel.0[1], pixel.0[2], pixel.0[3])
+                })
+                .collect()
+        };
+
+        let brick_colours = get_colours(&bricks);
+        let mut cat_colours = get_colours(&cat);
+        cat_colours = cat_colours.iter().map(|f| f.with_alpha(1.0)).collect();
+        let mut blended_image = RgbaImage::new(512, 512);
+
+        for (index, (base, blend)) in brick_colours.iter().zip(cat_colours).enumerate() {
+            let x = index as u32 % 512;
+            let y = index as u32 / 512;
+            blended_image.put_pixel(x, y, Rgba(base.blend(blend, BlendMode::Darken).into()));
+        }
+        blended_image.save_with_format("test/blended.png", ImageFormat::Png)?;
+        Ok(())
+    }
+
+    #[test]
+    pub fn blend_unchanged_test() {
+        use super::{colour_burn, colour_dodge, lum, sat, set_lum, set_sat};
+        use strum::IntoEnumIterator;
+
+        // `blend` as it was before being rewritten channel by channel
+        fn reference_blend(base: Colour, other: Colour, blend_mode: BlendMode) -> Colour {
+            let blended = match blend_mode {
+                BlendMode::Normal => other,
+                BlendMode::Addition => base + other,
+                BlendMode::Subtract => base - other,
+                BlendMode::Multiply => base * other,
+                BlendMode::Divide => base / other,
+                BlendMode::Darken => base.map_with(other, |base, blend| base.min(blend)),
+                BlendMode::Lighten => base.map_with(other, |base, blend| base.max(blend)),
+                BlendMode::Screen => -(-base * -other),
+                BlendMode::Overlay => base.map_with(other, |base, blend| {
+                    if base < 0.5f64 {
+                        2f64 * blend * base
+                    } else {
+                        1f64 - 2f64 * (1f64 - base) * (1f64 - blend)
+                    }
+                }),
+                BlendMode::HardLight => reference_blend(other, base, BlendMode::Overlay),
+                BlendMode::SoftLight => base * -(-other * -other) + -base * other,
+                BlendMode::LinearBurn => base + other - 1f64,
+                BlendMode::LinearLight => base + 2f64 * other - 1f64,
+                BlendMode::VividLight => base.map_with(other, |base, blend| {
+                    if blend <= 0.5f64 {
+                        colour_burn(base, 2f64 * blend)
+                    } else {
+                        colour_dodge(base, 2f64 * (blend - 0.5f64))
+                    }
+                }),
+                BlendMode::PinLight => base.map_with(other, |base, blend| {
+                    if blend <= 0.5f64 {
+                        base.min(2f64 * blend)
+                    } else {
+                        base.max(2f64 * blend - 1f64)
+                    }
+                }),
+                BlendMode::Hue => set_lum(set_sat(other, sat(base)), lum(base)),
+                BlendMode::Saturation => set_lum(set_sat(base, sat(other)), lum(base)),
+                BlendMode::Color => set_lum(other, lum(base)),
+                BlendMode::Luminosity => set_lum(base, lum(other)),
+            }
+            .cleaned();
+            let alpha_composite = other.a + base.a * (1f64 - other.a);
+            ((blended * other.a + base * base.a * (1f64 - other.a)) / (alpha_composite))
+                .with_alpha(alpha_composite)
+        }
+
+        // Exact comparison, only treating every NaN as the same
+        let bits = |colour: Colour| {
+            [colour.r, colour.g, colour.b, colour.a]
+                .map(|v| if v.is_nan() { u64::MAX } else { v.to_bits() })
+        };
+        let edges = [
+            Colour::transparent(),
+            Colour::grey(0.0),
+            Colour::grey(1.0),
+            Colour::grey(0.5).with_alpha(0.5),
+            Colour::solid(0.0, 0.5, 1.0).with_alpha(0.0),
+        ];
+        for blend_mode in BlendMode::iter() {
+            let mut rng = rand::rng();
+            let random =
+                (0..2000).map(|_| (Colour::random_rgba(&mut rng), Colour::random_rgba(&mut rng)));
+            let edge = edges
+                .iter()
+                .flat_map(|&base| edges.iter().map(move |&blend| (base, blend)));
+            for (base, blend) in random.chain(edge) {
+                let (blended, expected) = (
+                    base.blend(blend, blend_mode),
+                    reference_blend(base, blend, blend_mode),
+                );
+                assert_eq!(
+                    bits(blended),
+                    bits(expected),
+                    "{:?}: ({:?} blends onto {:?})",
+                    blend_mode,
+                    blend,
+                    base
+                );
+            }
+        }
+    }
+
+    #[test]
+    pub fn blend_linear_test() {
+        let grey = Colour::grey(0.5);
+        let gamma = grey.blend(grey, BlendMode::Multiply);
+        let linear = grey.blend_linear(grey, BlendMode::Multiply);
+        assert_relative_eq!(gamma, Colour::grey(0.25));
+        // (0.5 decoded)² ≈ 0.0458 of the light, encoded again
+        assert_relative_eq!(
+            linear,
+            Colour::grey(0.214041140482232).map(|v| v * v).to_srgb()
+        );
+        assert_relative_eq!(linear.r, 0.2370, epsilon = 1e-4);
+        assert!(linear.r < gamma.r);
+
+        // Opaque Normal and untouched black and white are the same either way
+        for blend_mode in [BlendMode::Normal, BlendMode::Multiply, BlendMode::Screen] {
+            assert_relative_eq!(
+                Colour::grey(0.0).blend_linear(Colour::grey(1.0), blend_mode),
+                Colour::grey(0.0).blend(Colour::grey(1.0), blend_mode)
+            );
+        }
+        assert_relative_eq!(
+            Colour::red(1.0).blend_linear(Colour::solid(0.2, 0.4, 0.6), BlendMode::Normal),
+            Colour::solid(0.2, 0.4, 0.6),
+            epsilon = 1e-12
+        );
+        // Alpha itself isn't gamma encoded
+        let translucent = Colour::grey(0.5).with_alpha(0.25);
+        assert_relative_eq!(
+            grey.with_alpha(0.5)
+                .blend_linear(translucent, BlendMode::Normal)
+                .a,
+            grey.with_alpha(0.5).blend(translucent, BlendMode::Normal).a
+        );
+    }
+
+    #[test]
+    pub fn blend_in_test() {
+        use strum::IntoEnumIterator;
+
+        let (base, blend) = (
+            Colour::new(0.8, 0.4, 0.2, 0.9),
+            Colour::new(0.2, 0.6, 0.5, 0.6),
+        );
+        for blend_mode in BlendMode::iter() {
+            assert_eq!(
+                base.blend_in(blend, blend_mode, BlendSpace::Srgb),
+                base.blend(blend, blend_mode)
+            );
+            assert_eq!(
+                base.blend_in(blend, blend_mode, BlendSpace::Linear),
+                base.blend_linear(blend, blend_mode)
+            );
+        }
+
+        // Screen brightens less in linear light once encoded again
+        let grey = Colour::grey(0.5);
+        let srgb = grey.blend_in(grey, BlendMode::Screen, BlendSpace::Srgb);
+        let linear = grey.blend_in(grey, BlendMode::Screen, BlendSpace::Linear);
+        assert_relative_eq!(srgb, Colour::grey(0.75));
+        assert!((srgb.r - linear.r).abs() > 0.05);
+        assert_relative_eq!(
+            linear,
+            Colour::grey(1.0 - 0.785958859517768 * 0.785958859517768).to_srgb()
+        );
+    }
+
+    #[test]
+    pub fn blend_strict_test() {
+        use strum::IntoEnumIterator;
+
+        let (base, blend) = (
+            Colour::new(0.8, 0.4, 0.2, 0.9),
+            Colour::new(0.0, 0.6, 0.0, 0.6),
+        );
+        for blend_mode in BlendMode::iter() {
+            let strict = base.blend_strict(blend, blend_mode).unwrap();
+            let lenient = base.blend(blend, blend_mode);
+            assert_eq!(
+                [strict.r, strict.g, strict.b, strict.a].map(f64::to_bits),
+                [lenient.r, lenient.g, lenient.b, lenient.a].map(f64::to_bits)
+            );
+        }
+
+        for bad in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            for colour in [
+                Colour::red(bad),
+                Colour::solid(0.5, 0.5, bad),
+                Colour::grey(0.5).with_alpha(bad),
+            ] {
+                assert_eq!(
+                    base.blend_strict(colour, BlendMode::Normal),
+                    Err(ColourError::NonFiniteChannel)
+                );
+                assert_eq!(
+                    colour.blend_strict(base, BlendMode::Screen),
+                    Err(ColourError::NonFiniteChannel)
+                );
+                // ...whereas blend cleans bad colour channels
+                if colour.a.is_finite() {
+                    assert!(
+                        base.blend(colour, BlendMode::Normal)
+                            .all_rgba(f64::is_finite)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    pub fn composite_over_solid_test() {
+        use strum::IntoEnumIterator;
+
+        let translucent_red = Colour::red(1.0).with_alpha(0.5);
+        let white = Colour::grey(1.0);
+        assert_relative_eq!(
+            translucent_red.composite_over_solid(white, BlendMode::Multiply),
+            Colour::solid(1.0, 0.5, 0.5)
+        );
+        // A translucent background is treated as opaque
+        assert_relative_eq!(
+            translucent_red.composite_over_solid(white.with_alpha(0.0), BlendMode::Multiply),
+            Colour::solid(1.0, 0.5, 0.5)
+        );
+
+        let layer = Colour::new(0.8, 0.4, 0.2, 0.7);
+        let background = Colour::solid(0.2, 0.6, 0.5);
+        for blend_mode in BlendMode::iter() {
+            let flattened = layer.composite_over_solid(background, blend_mode);
+            assert_eq!(flattened.a, 1.0);
+            assert_relative_eq!(flattened, layer.blend_onto(background, blend_mode));
+        }
+    }
+
+    #[test]
+    pub fn blend_clamped_test() {
+        use strum::IntoEnumIterator;
+
+        // In range blends are untouched
+        let (base, blend) = (
+            Colour::new(0.8, 0.4, 0.2, 0.9),
+            Colour::new(0.2, 0.6, 0.5, 0.6),
+        );
+        for blend_mode in BlendMode::iter() {
+            let (blended, _) = base.blend_components(blend, blend_mode);
+            if blended.all(|v| (0.0..=1.0).contains(&v)) {
+                assert_eq!(
+                    base.blend_clamped(blend, blend_mode),
+                    base.blend(blend, blend_mode)
+                );
+            }
+        }
+
+        // Over 1 inputs are clamped before compositing, not after
+        let (bright, blend) = (Colour::grey(1.5), Colour::grey(0.75).with_alpha(0.5));
+        assert_relative_eq!(
+            bright.blend(blend, BlendMode::Addition),
+            Colour::grey(1.875)
+        );
+        assert_relative_eq!(
+            bright.blend_clamped(blend, BlendMode::Addition),
+            Colour::grey(1.25)
+        );
+        assert_relative_eq!(
+            Colour::grey(0.5).blend_clamped(Colour::grey(2.0), BlendMode::Multiply),
+            Colour::grey(1.0)
+        );
+        assert_relative_eq!(
+            Colour::grey(0.25).blend_clamped(Colour::grey(-1.0).with_alpha(0.5), BlendMode::Normal),
+            Colour::grey(0.125)
+        );
+        // Chained additions stay in range
+        let mut colour = Colour::grey(0.5);
+        for _ in 0..5 {
+            colour = colour.blend_clamped(Colour::grey(0.4), BlendMode::Addition);
+            assert!(colour.all_rgba(|v| (0.0..=1.0).contains(&v)));
+        }
+        assert_eq!(colour, Colour::grey(1.0));
+    }
+
+    #[test]
+    pub fn blend_clipped_test() {
+        use strum::IntoEnumIterator;
+
+        let blend = Colour::new(0.2, 0.6, 0.5, 0.6);
+        for blend_mode in BlendMode::iter() {
+            // Nothing shows over a transparent base
+            let clipped = Colour::transparent().blend_clipped(blend, blend_mode);
+            assert_eq!(clipped.a, 0.0);
+            assert!(clipped.all(f64::is_finite));
+            // ...and an opaque base is a normal blend
+            let base = Colour::solid(0.8, 0.4, 0.2);
+            assert_relative_eq!(
+                base.blend_clipped(blend, blend_mode),
+                base.blend(blend, blend_mode),
+                epsilon = 1e-12
+            );
+            // The base's alpha is always kept
+            assert_eq!(base.with_alpha(0.3).blend_clipped(blend, blend_mode).a, 0.3);
+        }
+
+        let base = Colour::grey(0.5).with_alpha(0.5);
+        assert_relative_eq!(
+            base.blend_clipped(Colour::grey(1.0).with_alpha(0.5), BlendMode::Multiply),
+            Colour::grey(0.5).with_alpha(0.5)
+        );
+        assert_relative_eq!(
+            base.blend_clipped(Colour::grey(1.0).with_alpha(0.5), BlendMode::Screen),
+            Colour::grey(0.75).with_alpha(0.5)
+        );
+    }
+
+    #[test]
+    pub fn blend_with_opacity_test() {
+        use strum::IntoEnumIterator;
+
+        let blend = Colour::new(0.2, 0.6, 0.5, 0.8);
+        for blend_mode in BlendMode::iter() {
+            for base in [Colour::new(0.8, 0.4, 0.2, 0.6), Colour::transparent()] {
+                assert_eq!(base.blend_with_opacity(blend, blend_mode, 0.0), base);
+                assert_eq!(base.blend_with_opacity(blend, blend_mode, -1.0), base);
+                assert_eq!(
+                    base.blend_with_opacity(blend, blend_mode, 1.0),
+                    base.blend(blend, blend_mode)
+                );
+                assert_eq!(
+                    base.blend_with_opacity(blend, blend_mode, 2.0),
+                    base.blend(blend, blend_mode)
+                );
+            }
+        }
+
+        // Half opacity on a half transparent layer is a quarter of the layer
+        let base = Colour::grey(0.0);
+        let half =
+            base.blend_with_opacity(Colour::grey(1.0).with_alpha(0.5), BlendMode::Normal, 0.5);
+        assert_relative_eq!(half, Colour::grey(0.25));
+        let screened = base.blend_with_opacity(Colour::grey(0.5), BlendMode::Screen, 0.5);
+        assert_relative_eq!(screened, Colour::grey(0.25));
+    }
+
+    #[test]
+    pub fn blend_mode_parse_test() {
+        use strum::IntoEnumIterator;
+
+        for blend_mode in BlendMode::iter() {
+            let name = blend_mode.to_string();
+            assert_eq!(name.parse::<BlendMode>(), Ok(blend_mode));
+            assert_eq!(
+                name.to_ascii_uppercase().parse::<BlendMode>(),
+                Ok(blend_mode)
+            );
+            assert_eq!(
+                format!("{blend_mode:?}").parse::<BlendMode>(),
+                Ok(blend_mode)
+            );
+        }
+        for name in [
+            "hard-light",
+            "hardlight",
+            "HardLight",
+            " hard_light ",
+            "Hard Light",
+        ] {
+            assert_eq!(
+                name.parse::<BlendMode>(),
+                Ok(BlendMode::HardLight),
+                "{name}"
+            );
+        }
+        assert_eq!("multiply".parse::<BlendMode>(), Ok(BlendMode::Multiply));
+        assert_eq!("Screen".parse::<BlendMode>(), Ok(BlendMode::Screen));
+        assert_eq!("colour".parse::<BlendMode>(), Ok(BlendMode::Color));
+
+        assert_eq!(
+            "dissolve".parse::<BlendMode>(),
+            Err("`dissolve` is not a blend mode.".to_string())
+        );
+        assert!("".parse::<BlendMode>().is_err());
+        assert!("hard--light!".parse::<BlendMode>().is_err());
+    }
+
+    #[test]
+    pub fn blend_rgb_test() {
+        use strum::IntoEnumIterator;
+
+        let (base, blend) = (0.6, 0.3);
+        let blended = |mode: BlendMode| {
+            Colour::grey(base)
+                .with_alpha(0.7)
+                .blend_rgb(Colour::grey(blend), mode)
+        };
+        let expected = [
+            (BlendMode::Normal, blend),
+            (BlendMode::Addition, base + blend),
+            (BlendMode::Subtract, base - blend),
+            (BlendMode::Multiply, base * blend),
+            (BlendMode::Divide, base / blend),
+            (BlendMode::Darken, blend),
+            (BlendMode::Lighten, base),
+            (BlendMode::Screen, 1.0 - (1.0 - base) * (1.0 - blend)),
+            (BlendMode::Overlay, 1.0 - 2.0 * (1.0 - base) * (1.0 - blend)),
+            (BlendMode::HardLight, 2.0 * base * blend),
+            (
+                BlendMode::SoftLight,
+                base * (1.0 - (1.0 - blend).powi(2)) + (1.0 - base) * blend,
+            ),
+            (BlendMode::LinearBurn, base + blend - 1.0),
+            (BlendMode::LinearLight, base + 2.0 * blend - 1.0),
+            (BlendMode::PinLight, base.min(2.0 * blend)),
+        ];
+        for (mode, value) in expected {
+            assert_relative_eq!(
+                blended(mode),
+                Colour::grey(value).with_alpha(0.7),
+                epsilon = 1e-12
+            );
+        }
+
+        // The non-separable modes of greys are greys of the base or blend lum
+        assert_relative_eq!(
+            blended(BlendMode::Luminosity),
+            Colour::grey(blend).with_alpha(0.7)
+        );
+        assert_relative_eq!(
+            blended(BlendMode::Color),
+            Colour::grey(base).with_alpha(0.7)
+        );
+
+        // Neither alpha changes the blended channels
+        for mode in BlendMode::iter() {
+            let opaque = Colour::grey(base).blend_rgb(Colour::grey(blend), mode);
+            for (base_alpha, blend_alpha) in [(0.7, 1.0), (1.0, 0.2), (0.7, 0.2), (0.2, 0.7)] {
+                let rgb = Colour::grey(base)
+                    .with_alpha(base_alpha)
+                    .blend_rgb(Colour::grey(blend).with_alpha(blend_alpha), mode);
+                assert_relative_eq!(rgb, opaque.with_alpha(base_alpha), epsilon = 1e-12);
+            }
+        }
+
+        // `.blend()` is this composited
+        let (base, blend) = (
+            Colour::new(0.8, 0.4, 0.2, 0.7),
+            Colour::new(0.2, 0.6, 1.0, 0.4),
+        );
+        for mode in BlendMode::iter() {
+            let rgb = base.blend_rgb(blend, mode);
+            assert_eq!(rgb.a, base.a);
+            let (blended, _) = base.blend_components(blend, mode);
+            assert_eq!(rgb.with_alpha(blend.a), blended.cleaned());
+        }
+        assert_eq!(
+            Colour::grey(0.5).blend_rgb(Colour::grey(0.0), BlendMode::Divide),
+            Colour::grey(1.0)
+        );
+    }
+
+    #[test]
+    pub fn blend_components_test() {
+        use strum::IntoEnumIterator;
+        let bits = |c: Colour| [c.r, c.g, c.b, c.a].map(f64::to_bits);
+
+        let layers = [
+            (
+                Colour::new(0.8, 0.4, 0.2, 0.7),
+                Colour::new(0.2, 0.6, 1.0, 0.4),
+            ),
+            (Colour::grey(0.5), Colour::grey(0.0)),
+            (Colour::transparent(), Colour::red(1.0).with_alpha(0.5)),
+        ];
+        for blend_mode in BlendMode::iter() {
+            for (base, blend) in layers {
+                let (blended, composited) = base.blend_components(blend, blend_mode);
+                assert_eq!(bits(composited), bits(base.blend(blend, blend_mode)));
+                assert_eq!(blended.a, blend.a);
+            }
+        }
+
+        // A fully opaque blend layer composites to exactly the blended colour
+        let (blended, composited) = Colour::solid(0.8, 0.4, 0.2)
+            .blend_components(Colour::solid(0.2, 0.6, 1.0), BlendMode::Screen);
+        assert_relative_eq!(blended, composited);
+        assert_relative_eq!(blended, Colour::solid(0.84, 0.76, 1.0));
+
+        // Hard light of translucent layers is blended before any compositing
+        let (base, blend) = (
+            Colour::grey(0.6).with_alpha(0.5),
+            Colour::grey(0.3).with_alpha(0.4),
+        );
+        let (blended, composited) = base.blend_components(blend, BlendMode::HardLight);
+        assert_relative_eq!(blended, Colour::grey(0.36).with_alpha(0.4));
+        assert_eq!(
+            bits(composited),
+            bits(base.blend(blend, BlendMode::HardLight))
+        );
+
+        // Division by zero is visible before compositing cleans it
+        let (blended, composited) =
+            Colour::grey(0.5).blend_components(Colour::grey(0.0), BlendMode::Divide);
+        assert!(blended.r.is_infinite());
+        assert!(composited.r.is_finite());
+    }
+
+    #[test]
+    pub fn light_blend_test() {
+        let base = Colour::grey(0.5);
+        let blend = |mode: BlendMode, value: f64| base.blend(Colour::grey(value), mode).r;
+
+        assert_relative_eq!(blend(BlendMode::LinearBurn, 0.2), -0.3);
+        assert_relative_eq!(blend(BlendMode::LinearBurn, 0.8), 0.3);
+
+        assert_relative_eq!(blend(BlendMode::LinearLight, 0.2), -0.1);
+        assert_relative_eq!(blend(BlendMode::LinearLight, 0.6), 0.7);
+        assert_relative_eq!(blend(BlendMode::LinearLight, 0.8), 1.1);
+
+        // burn(0.5, 0.6) = 1 - 0.5 / 0.6 and dodge(0.5, 0.2) = 0.5 / 0.8
+        assert_relative_eq!(blend(BlendMode::VividLight, 0.3), 1.0 / 6.0);
+        assert_relative_eq!(blend(BlendMode::VividLight, 0.6), 0.625);
+        assert_relative_eq!(blend(BlendMode::VividLight, 0.2), 0.0);
+        assert_relative_eq!(blend(BlendMode::VividLight, 0.9), 1.0);
+
+        assert_relative_eq!(blend(BlendMode::PinLight, 0.2), 0.4);
+        assert_relative_eq!(blend(BlendMode::PinLight, 0.3), 0.5);
+        assert_relative_eq!(blend(BlendMode::PinLight, 0.6), 0.5);
+        assert_relative_eq!(blend(BlendMode::PinLight, 0.8), 0.6);
+
+        // Composited like every other mode
+        assert_relative_eq!(
+            base.blend(Colour::grey(0.8).with_alpha(0.5), BlendMode::PinLight),
+            Colour::grey(0.55)
+        );
+    }
+
+    #[test]
+    pub fn hsl_blend_test() {
+        use super::lum;
+
+        let base = Colour::solid(0.8, 0.4, 0.2);
+        let blend = Colour::solid(0.2, 0.6, 1.0);
+        assert_relative_eq!(
+            base.blend(blend, BlendMode::Hue),
+            Colour::solid(0.255, 0.555, 0.855)
+        );
+        assert_relative_eq!(
+            base.blend(blend, BlendMode::Saturation),
+            Colour::solid(0.9006666666666667, 0.36733333333333335, 0.10066666666666668)
+        );
+        assert_relative_eq!(
+            base.blend(blend, BlendMode::Color),
+            Colour::solid(0.174, 0.574, 0.974)
+        );
+        assert_relative_eq!(
+            base.blend(blend, BlendMode::Luminosity),
+            Colour::solid(0.826, 0.426, 0.226)
+        );
+
+        // Clipping a colour that would overshoot keeps its luminosity
+        assert_relative_eq!(
+            Colour::grey(0.5).blend(Colour::red(1.0), BlendMode::Color),
+            Colour::solid(1.0, 2.0 / 7.0, 2.0 / 7.0)
+        );
+
+        // Tinting a greyscale image red preserves its luminosity
+        for grey in [0.1, 0.25, 0.5, 0.75, 0.9] {
+            let pixel = Colour::grey(grey);
+            let tinted = pixel.blend(Colour::red(1.0), BlendMode::Color);
+            assert_relative_eq!(lum(tinted), lum(pixel), epsilon = 1e-9);
+            assert!(tinted.r > tinted.g && tinted.g == tinted.b);
+        }
+    }
+
+    #[test]
+    pub fn xterm256_test() {
+        for index in 16..=255 {
+            assert_eq!(Colour::from_xterm256(index).to_xterm256(), index);
+        }
+        assert_eq!(Colour::from_xterm256(0), Colour::grey(0.0));
+        assert_eq!(Colour::from_xterm256(9), Colour::from_u8(255, 128, 128));
+        assert_eq!(Colour::from_xterm256(16), Colour::grey(0.0));
+        assert_eq!(Colour::from_xterm256(21), Colour::blue(1.0));
+        assert_eq!(Colour::from_xterm256(46), Colour::green(1.0));
+        assert_eq!(Colour::from_xterm256(110), Colour::from_u8(135, 175, 215));
+        assert_eq!(Colour::from_xterm256(231), Colour::grey(1.0));
+        assert_eq!(Colour::from_xterm256(232), Colour::from_u8(8, 8, 8));
+        assert_eq!(Colour::from_xterm256(255), Colour::from_u8(238, 238, 238));
+
+        // Nearest matches
+        assert_eq!(Colour::from_u8(250, 5, 3).to_xterm256(), 196);
+        assert_eq!(Colour::from_u8(100, 101, 99).to_xterm256(), 241);
+        assert_eq!(Colour::from_u8(2, 2, 2).to_xterm256(), 16);
+        assert_eq!(Colour::from_u8(255, 255, 250).to_xterm256(), 231);
+    }
+
+    #[test]
+    pub fn ansi256_test() {
+        assert_eq!(Colour::red(1.0).ansi256_fg(), "\x1b[38;5;196m");
+        assert_eq!(Colour::red(1.0).ansi256_bg(), "\x1b[48;5;196m");
+        assert_eq!(Colour::grey(0.5).ansi256_fg(), "\x1b[38;5;244m");
+        assert_eq!(
+            Colour::from_u8(135, 175, 215).ansi256_bg(),
+            "\x1b[48;5;110m"
+        );
+    }
+
+    #[cfg(feature = "ratatui")]
+    #[test]
+    pub fn ratatui_indexed_test() {
+        use ratatui::style::Color;
+
+        assert_eq!(Colour::from(Color::Indexed(1)), Colour::from(Color::Red));
+        assert_eq!(
+            Colour::from(Color::Indexed(8)),
+            Colour::from(Color::DarkGray)
+        );
+        assert_eq!(Colour::from(Color::Indexed(15)), Colour::from(Color::White));
+        assert_eq!(Colour::from(Color::Indexed(196)), Colour::red(1.0));
+        assert_eq!(
+            Colour::from(Color::Indexed(67)),
+            Colour::from_u8(95, 135, 175)
+        );
+    }
+
+    #[test]
+    pub fn cubic_bezier_test() {
+        use super::cubic_bezier;
+
+        let ease = |t| cubic_bezier(t, (0.25, 0.1), (0.25, 1.0));
+        assert_relative_eq!(ease(0.5), 0.802403387584857, epsilon = 1e-9);
+        assert_relative_eq!(ease(0.0), 0.0);
+        assert_relative_eq!(ease(1.0), 1.0);
+
+        // `linear` and clamping
+        assert_relative_eq!(
+            cubic_bezier(0.3, (0.0, 0.0), (1.0, 1.0)),
+            0.3,
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(cubic_bezier(1.5, (0.42, 0.0), (0.58, 1.0)), 1.0);
+        // `ease-in-out` is symmetric
+        let ease_in_out = |t| cubic_bezier(t, (0.42, 0.0), (0.58, 1.0));
+        assert_relative_eq!(ease_in_out(0.5), 0.5, epsilon = 1e-9);
+        assert_relative_eq!(ease_in_out(0.2) + ease_in_out(0.8), 1.0, epsilon = 1e-9);
+        // Flat starts still converge
+        assert_relative_eq!(
+            cubic_bezier(0.01, (1.0, 0.0), (1.0, 0.0)),
+            0.0,
+            epsilon = 1e-3
+        );
+
+        let (from, to) = (Colour::red(1.0), Colour::blue(1.0));
+        assert_relative_eq!(
+            from.lerp_cubic_bezier(to, 0.5, (0.25, 0.1), (0.25, 1.0)),
+            from.lerp(to, ease(0.5))
+        );
+    }
+
+    #[test]
+    pub fn buffer_test() {
+        use crate::{
+            ColourError, colours_from_f64, colours_from_u8, colours_to_f64, colours_to_u8,
+        };
+
+        let colours = vec![
+            Colour::new(0.1, 0.2, 0.3, 0.4),
+            Colour::grey(0.5),
+            Colour::transparent(),
+        ];
+        let buffer = colours_to_f64(&colours);
+        assert_eq!(buffer.len(), 12);
+        assert_eq!(colours_from_f64(&buffer).unwrap(), colours);
+        assert_eq!(colours_from_f64(&[]).unwrap(), vec![]);
+        assert_eq!(
+            colours_from_f64(&buffer[..10]),
+            Err(ColourError::InvalidBufferLength(10))
+        );
+
+        let bytes: Vec<u8> = (0..=255).collect();
+        let colours = colours_from_u8(&bytes).unwrap();
+        assert_eq!(colours.len(), 64);
+        assert_eq!(colours[0], Colour::from_u8_rgba(0, 1, 2, 3));
+        assert_eq!(colours_to_u8(&colours), bytes);
+        assert_eq!(
+            colours_from_u8(&bytes[..3]),
+            Err(ColourError::InvalidBufferLength(3))
+        );
+    }
+
+    #[test]
+    pub fn oklab_test() {
+        // The sRGB primaries in OKLab
+        let known = [
+            (Colour::red(1.0), (0.627955, 0.224863, 0.125846)),
+            (Colour::green(1.0), (0.866440, -0.233888, 0.179498)),
+            (Colour::blue(1.0), (0.452014, -0.032457, -0.311528)),
+            (Colour::grey(0.0), (0.0, 0.0, 0.0)),
+        ];
+        for (colour, (l, a, b)) in known {
+            let oklab = colour.to_oklab();
+            assert_relative_eq!(oklab.0, l, epsilon = 1e-5);
+            assert_relative_eq!(oklab.1, a, epsilon = 1e-5);
+            assert_relative_eq!(oklab.2, b, epsilon = 1e-5);
+            assert_relative_eq!(Colour::from_oklab(l, a, b), colour, epsilon = 1e-4);
+        }
+
+        // Greys are achromatic and lightness rises with them
+        let mut previous = -1.0;
+        for i in 0..=10 {
+            let (l, a, b) = Colour::grey(i as f64 / 10.0).to_oklab();
+            assert_relative_eq!(a, 0.0, epsilon = 1e-6);
+            assert_relative_eq!(b, 0.0, epsilon = 1e-6);
+            assert!(l > previous);
+            previous = l;
+        }
+
+        // Alpha is dropped and defaulted
+        let (l, a, b) = Colour::new(0.2, 0.4, 0.6, 0.1).to_oklab();
+        assert_relative_eq!(
+            Colour::from_oklab(l, a, b),
+            Colour::solid(0.2, 0.4, 0.6),
+            epsilon = 1e-9
+        );
+    }
+
+    #[test]
+    pub fn oklch_test() {
+        // Greys are achromatic, with no hue rather than NaN
+        for i in 0..=10 {
+            let (l, c, h) = Colour::grey(i as f64 / 10.0).to_oklch();
+            assert_relative_eq!(c, 0.0, epsilon = 1e-6);
+            assert_eq!(h, 0.0);
+            assert_relative_eq!(
+                Colour::from_oklch(l, c, h),
+                Colour::grey(i as f64 / 10.0),
+                epsilon = 1e-6
+            );
+        }
+
+        let (l, c, h) = Colour::red(1.0).to_oklch();
+        assert_relative_eq!(l, 0.62796, epsilon = 1e-5);
+        assert_relative_eq!(c, 0.25768, epsilon = 1e-5);
+        assert_relative_eq!(h, 29.2339, epsilon = 1e-4);
+        assert_relative_eq!(
+            Colour::from_oklch(l, c, h),
+            Colour::red(1.0),
+            epsilon = 1e-6
+        );
+
+        // Hues are wrapped into [0, 360)
+        let (_, _, h) = Colour::solid(1.0, 0.0, 0.5).to_oklch();
+        assert!((0.0..360.0).contains(&h));
+        let (_, _, h) = Colour::blue(1.0).to_oklch();
+        assert_relative_eq!(h, 264.052, epsilon = 1e-3);
+        assert_relative_eq!(
+            Colour::from_oklch(l, c, h - 360.0),
+            Colour::from_oklch(l, c, h),
+            epsilon = 1e-9
+        );
+    }
+
+    #[test]
+    pub fn clamp_chroma_test() {
+        let vivid = Colour::new(1.0, 0.0, 1.0, 0.5);
+        let (l, c, h) = vivid.to_oklch();
+        assert!(c > 0.3);
+        for max_chroma in [0.05, 0.1, 0.2] {
+            let clamped = vivid.clamp_chroma(max_chroma);
+            let (clamped_l, clamped_c, clamped_h) = clamped.to_oklch();
+            assert_relative_eq!(clamped_l, l, epsilon = 1e-6);
+            assert_relative_eq!(clamped_c, max_chroma, epsilon = 1e-6);
+            assert_relative_eq!(clamped_h, h, epsilon = 1e-4);
+            assert_eq!(clamped.a, 0.5);
+            // Muting a colour keeps it in gamut
+            assert!(clamped.all(|v| (-1e-9..=1.0 + 1e-9).contains(&v)));
+        }
+        // A chroma of 0 is a grey of the same lightness
+        let (_, c, _) = vivid.clamp_chroma(0.0).to_oklch();
+        assert_relative_eq!(c, 0.0, epsilon = 1e-6);
+        // Already muted colours are untouched
+        let muted = Colour::solid(0.5, 0.45, 0.45);
+        assert_eq!(muted.clamp_chroma(0.2), muted);
+    }
+
+    #[test]
+    pub fn oklch_mix_test() {
+        let chroma = |colour: Colour| {
+            let (_, a, b) = colour.to_oklab();
+            a.hypot(b)
+        };
+        let (red, green) = (Colour::red(1.0), Colour::green(1.0));
+
+        // Chroma is interpolated, so the midpoint is as vivid as the ends
+        let mixed = red.mix_oklch(green, 0.5);
+        assert_relative_eq!(
+            chroma(mixed),
+            (chroma(red) + chroma(green)) / 2.0,
+            epsilon = 1e-6
+        );
+        // ...whereas mixing in RGB loses almost half the chroma
+        assert!(chroma(red.lerp(green, 0.5)) < 0.6 * chroma(mixed));
+        assert!(mixed.max_channel() - mixed.r.min(mixed.g.min(mixed.b)) > 0.5);
+
+        // Greys adopt the other colour's hue instead of swinging through others
+        let to_grey = red.mix_oklch(Colour::grey(0.5), 0.5);
+        let (_, a, b) = to_grey.to_oklab();
+        let (_, red_a, red_b) = red.to_oklab();
+        assert_relative_eq!(b.atan2(a), red_b.atan2(red_a), epsilon = 1e-6);
+
+        assert_relative_eq!(
+            red.with_alpha(0.0).mix_oklch(green, 0.25).a,
+            0.25,
+            epsilon = 1e-6
+        );
+    }
+
+    #[test]
+    pub fn mix_test() {
+        use strum::IntoEnumIterator;
+
+        let (from, to) = (
+            Colour::new(0.8, 0.4, 0.2, 0.0),
+            Colour::new(0.2, 0.6, 1.0, 1.0),
+        );
+        for space in MixSpace::iter() {
+            assert_relative_eq!(from.mix(&to, 0.0, space), from, epsilon = 1e-6);
+            assert_relative_eq!(from.mix(&to, 1.0, space), to, epsilon = 1e-6);
+            assert_relative_eq!(from.mix(&to, 0.25, space).a, 0.25);
+        }
+
+        let (red, green) = (Colour::red(1.0), Colour::green(1.0));
+        for t in [0.0, 0.3, 0.5, 0.9] {
+            assert_relative_eq!(red.mix(&green, t, MixSpace::Rgb), red.lerp(green, t));
+        }
+        // Through yellow rather than a muddy brown
+        let yellow = red.mix(&green, 0.5, MixSpace::Hsl);
+        assert_relative_eq!(yellow, Colour::solid(1.0, 1.0, 0.0), epsilon = 1e-12);
+        // The shorter way from red to blue is through magenta, not green
+        let magenta = red.mix(&Colour::blue(1.0), 0.5, MixSpace::Hsl);
+        assert_relative_eq!(magenta, Colour::solid(1.0, 0.0, 1.0), epsilon = 1e-12);
+        // Greys adopt the other colour's hue
+        let pale = green.mix(&Colour::grey(1.0), 0.5, MixSpace::Hsl);
+        assert_relative_eq!(pale, Colour::solid(0.625, 0.875, 0.625), epsilon = 1e-12);
+
+        let (black, white) = (Colour::grey(0.0), Colour::grey(1.0));
+        assert_relative_eq!(
+            black.mix(&white, 0.5, MixSpace::LinearRgb),
+            Colour::grey(0.5).to_srgb()
+        );
+        let (l, _, _) = black.mix(&white, 0.5, MixSpace::Oklab).to_oklab();
+        assert_relative_eq!(l, 0.5, epsilon = 1e-6);
+    }
+
+    #[test]
+    pub fn lerp_hsl_test() {
+        // 0° to 240° is shorter backwards through magenta than forwards
+        // through green
+        let (red, blue) = (Colour::red(1.0), Colour::blue(1.0).with_alpha(0.0));
+        assert_relative_eq!(red.lerp_hsl(blue, 0.5), Colour::new(1.0, 0.0, 1.0, 0.5));
+        for i in 0..=10 {
+            let t = i as f64 / 10.0;
+            let (h, s, l) = red.lerp_hsl(blue, t).to_hsl();
+            assert_relative_eq!(h, (360.0 - 120.0 * t) % 360.0, epsilon = 1e-9);
+            assert_relative_eq!(s, 1.0, epsilon = 1e-12);
+            assert_relative_eq!(l, 0.5, epsilon = 1e-12);
+            assert_ne!(red.lerp_hsl(blue, t).g, 1.0);
+        }
+        assert_relative_eq!(blue.lerp_hsl(red, 0.5), red.lerp_hsl(blue, 0.5));
+
+        // Red to cyan never passes through grey
+        let cyan = Colour::solid(0.0, 1.0, 1.0);
+        let midpoint = red.lerp_hsl(cyan, 0.5);
+        assert_relative_eq!(midpoint.to_hsl().1, 1.0, epsilon = 1e-12);
+        assert_relative_eq!(red.lerp_hsl(cyan, 0.0), red);
+        assert_relative_eq!(red.lerp_hsl(cyan, 1.0), cyan, epsilon = 1e-12);
+        assert_eq!(red.lerp_hsl(cyan, 0.3), red.mix(&cyan, 0.3, MixSpace::Hsl));
+    }
+
+    #[test]
+    pub fn opacity_predicates_test() {
+        let opaque = Colour::red(1.0);
+        assert!(opaque.is_opaque() && !opaque.is_transparent() && !opaque.is_translucent());
+        let transparent = Colour::transparent();
+        assert!(!transparent.is_opaque() && transparent.is_transparent());
+        assert!(!transparent.is_translucent());
+        let half = Colour::red(1.0).with_alpha(0.5);
+        assert!(!half.is_opaque() && !half.is_transparent() && half.is_translucent());
+
+        // Out of range alpha still counts
+        assert!(opaque.with_alpha(1.5).is_opaque());
+        assert!(opaque.with_alpha(-0.5).is_transparent());
+        assert!(!opaque.with_alpha(f64::NAN).is_translucent());
+
+        let almost_opaque = opaque.with_alpha(1.0 - 1e-12);
+        assert!(almost_opaque.is_translucent());
+        assert!(almost_opaque.is_opaque_within(1e-9));
+        assert!(!half.is_opaque_within(1e-9));
+        let almost_transparent = opaque.with_alpha(1e-12);
+        assert!(almost_transparent.is_transparent_within(1e-9));
+        assert!(!almost_transparent.is_transparent_within(0.0));
+        assert!(!half.is_transparent_within(1e-9));
+    }
+
+    #[test]
+    pub fn u8_saturating_test() {
+        assert_eq!(Colour::grey(0.999).to_u8_saturating(), (255, 255, 255));
+        assert_eq!(Colour::grey(0.999).as_u8(), (254, 254, 254));
+        assert_eq!(Colour::grey(0.5).to_u8_saturating(), (128, 128, 128));
+        assert_eq!(Colour::grey(0.001).to_u8_saturating(), (0, 0, 0));
+
+        // Out of range channels clamp rather than wrap
+        let out_of_range = Colour::new(-0.5, 1.5, 300.0, -1e9);
+        assert_eq!(out_of_range.to_u8_saturating(), (0, 255, 255));
+        assert_eq!(out_of_range.to_u8_rgba_saturating(), (0, 255, 255, 0));
+        assert_eq!(
+            Colour::grey(f64::NAN).to_u8_rgba_saturating(),
+            (0, 0, 0, 255)
+        );
+        assert_eq!(
+            Colour::grey(f64::INFINITY)
+                .with_alpha(f64::NEG_INFINITY)
+                .to_u8_rgba_saturating(),
+            (255, 255, 255, 0)
+        );
+
+        // Every level survives the round trip
+        for v in 0..=255 {
+            assert_eq!(
+                Colour::from_u8_rgba(v, v, v, v).to_u8_rgba_saturating(),
+                (v, v, v, v)
+            );
+        }
+    }
+
+    #[test]
+    pub fn constants_test() {
+        assert_eq!(Colour::WHITE, Colour::grey(1.0));
+        assert_eq!(Colour::BLACK, Colour::grey(0.0));
+        assert_eq!(Colour::RED, Colour::red(1.0));
+        assert_eq!(Colour::GREEN, Colour::green(1.0));
+        assert_eq!(Colour::BLUE, Colour::blue(1.0));
+        assert_eq!(Colour::YELLOW, Colour::solid(1.0, 1.0, 0.0));
+        assert_eq!(Colour::CYAN, Colour::solid(0.0, 1.0, 1.0));
+        assert_eq!(Colour::MAGENTA, Colour::solid(1.0, 0.0, 1.0));
+        assert_eq!(Colour::TRANSPARENT, Colour::transparent());
+        // The secondaries are the primaries' sums
+        assert_eq!(Colour::YELLOW, Colour::RED + Colour::GREEN);
+    }
+
+    #[test]
+    pub fn const_constructors_test() {
+        const GREY: Colour = Colour::grey(0.5);
+        const ACCENT: Colour = Colour::from_u8(255, 128, 0).with_alpha(0.5);
+        const PALETTE: [Colour; 3] = [
+            Colour::new(0.1, 0.2, 0.3, 0.4),
+            Colour::solid(0.1, 0.2, 0.3)
+                .with_red(1.0)
+                .with_green(0.0)
+                .with_blue(0.5),
+            Colour::transparent(),
+        ];
+        assert_eq!(GREY, Colour::solid(0.5, 0.5, 0.5));
+        assert_eq!(ACCENT, Colour::from_u8_rgba(255, 128, 0, 0).with_alpha(0.5));
+        assert_eq!(PALETTE[1], Colour::solid(1.0, 0.0, 0.5));
+        assert_eq!(PALETTE[2], Colour::TRANSPARENT);
+    }
+
+    #[test]
+    pub fn channel_test() {
+        let mut colour = Colour::new(0.1, 0.2, 0.3, 0.4);
+        for (i, expected) in [0.1, 0.2, 0.3, 0.4].into_iter().enumerate() {
+            assert_eq!(colour.channel(i), Some(expected));
+            assert_eq!(colour[i], expected);
+        }
+        assert_eq!(colour.channel(4), None);
+        assert_eq!(colour.channel_mut(4), None);
+
+        for i in 0..4 {
+            *colour.channel_mut(i).unwrap() += 0.5;
+        }
+        assert_relative_eq!(colour, Colour::new(0.6, 0.7, 0.8, 0.9));
+        for i in 0..4 {
+            colour[i] = i as f64;
+        }
+        assert_eq!(colour, Colour::new(0.0, 1.0, 2.0, 3.0));
+
+        assert_eq!(
+            Colour::new(-0.5, 0.5, 1.5, 2.0).clamp01(),
+            Colour::new(0.0, 0.5, 1.0, 1.0)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "the len is 4 but the index is 4")]
+    pub fn channel_index_out_of_bounds_test() {
+        let _ = Colour::grey(1.0)[4];
+    }
+
+    #[test]
+    #[should_panic(expected = "the len is 4 but the index is 7")]
+    pub fn channel_index_mut_out_of_bounds_test() {
+        let mut colour = Colour::grey(1.0);
+        colour[7] = 0.0;
+    }
+
+    #[test]
+    pub fn from_kelvin_test() {
+        let daylight = Colour::from_kelvin(6500.0);
+        assert!(daylight.max_channel() - daylight.b < 0.03, "{daylight}");
+        assert!(daylight.all(|v| v > 0.97));
+
+        let warm = Colour::from_kelvin(2000.0);
+        assert!(warm.r / warm.b > daylight.r / daylight.b);
+        assert!(warm.r > warm.g && warm.g > warm.b);
+        let cool = Colour::from_kelvin(10000.0);
+        assert!(cool.b > cool.g && cool.g > cool.r);
+
+        // Clamped to the range of the fit
+        assert_eq!(Colour::from_kelvin(0.0), Colour::from_kelvin(1000.0));
+        assert_eq!(Colour::from_kelvin(1e6), Colour::from_kelvin(40000.0));
+        for kelvin in (1000..=40000).step_by(500) {
+            let colour = Colour::from_kelvin(kelvin as f64);
+            assert!(colour.is_normal(), "{kelvin}K is {colour}");
+        }
+    }
+
+    #[test]
+    pub fn from_name_test() {
+        assert_eq!(
+            Colour::from_name("rebeccapurple"),
+            Some(Colour::from_u8(102, 51, 153))
+        );
+        assert_eq!(
+            Colour::from_name("CORNFLOWERBLUE"),
+            Some(Colour::from_u8(100, 149, 237))
+        );
+        assert_eq!(Colour::from_name("red"), Some(Colour::red(1.0)));
+        assert_eq!(Colour::from_name("Grey"), Colour::from_name("gray"));
+        assert_eq!(
+            Colour::from_name("aliceblue"),
+            Some(Colour::from_u8(240, 248, 255))
+        );
+        assert_eq!(
+            Colour::from_name("yellowgreen"),
+            Some(Colour::from_u8(154, 205, 50))
+        );
+
+        let transparent = Colour::from_name("Transparent").unwrap();
+        assert_eq!(transparent.a, 0.0);
+
+        for unknown in ["", "notacolour", "red ", "rebecca purple", "grey50"] {
+            assert_eq!(Colour::from_name(unknown), None, "{unknown}");
+        }
+
+        // Every name can be found, so the table must be sorted
+        for (name, (r, g, b)) in super::CSS_NAMED_COLOURS {
+            assert_eq!(
+                Colour::from_name(name),
+                Some(Colour::from_u8(r, g, b)),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    pub fn oklch_css_test() {
+        // Reference OKLCH values for the sRGB primaries and secondaries
+        let known = [
+            ("oklch(62.8% 0.2577 29.23)", Colour::red(1.0)),
+            ("oklch(86.64% 0.2948 142.5)", Colour::green(1.0)),
+            ("oklch(45.2% 0.3132 264.05)", Colour::blue(1.0)),
+            ("oklch(0.968 0.211 109.77)", Colour::solid(1.0, 1.0, 0.0)),
+            (
+                "OKLCH(90.54% 0.1546 194.77deg)",
+                Colour::solid(0.0, 1.0, 1.0),
+            ),
+            (
+                "oklch(0.7017 0.3225 0.9121turn)",
+                Colour::solid(1.0, 0.0, 1.0),
+            ),
+            ("oklab(0.628 0.2249 0.1258)", Colour::red(1.0)),
+            ("oklab(62.8% 56.2% 31.45%)", Colour::red(1.0)),
+        ];
+        for (css, expected) in known {
+            let colour = Colour::from_oklch_css(css).unwrap();
+            assert_relative_eq!(colour, expected, epsilon = 2e-3);
+        }
+
+        // Hue units all agree
+        let degrees = Colour::from_oklch_css("oklch(0.7 0.1 90)").unwrap();
+        for hue in ["90deg", "100grad", "0.25turn", "1.5707963267948966rad"] {
+            let css = format!("oklch(0.7 0.1 {hue})");
+            assert_relative_eq!(
+                Colour::from_oklch_css(&css).unwrap(),
+                degrees,
+                epsilon = 1e-9
+            );
+        }
+
+        // Greys, alpha and none
+        assert_relative_eq!(
+            Colour::from_oklch_css("oklch(100% 0 none)").unwrap(),
+            Colour::grey(1.0),
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(
+            Colour::from_oklch_css("  oklch(0% 0 0/0.25)  ").unwrap(),
+            Colour::grey(0.0).with_alpha(0.25),
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(
+            Colour::from_oklch_css("oklch(62.8% 0.2577 29.23 / 40%)")
+                .unwrap()
+                .a,
+            0.4
+        );
+        // Out of gamut colours are clamped
+        assert!(
+            Colour::from_oklch_css("oklch(0.9 0.4 150)")
+                .unwrap()
+                .is_normal()
+        );
+
+        for invalid in [
+            "oklch(0.5 0.1)",
+            "oklch(0.5 0.1 20 40)",
+            "oklch(0.5 0.1 20",
+            "lch(50% 30 20)",
+            "oklch(0.5 fast 20)",
+            "oklch(0.5 0.1 20px)",
+            "oklch(0.5 0.1 inf)",
+            "#ff0000",
+        ] {
+            assert!(Colour::from_oklch_css(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    pub fn hex_test() {
+        let known = [
+            ("#ff8000", Colour::from_u8(255, 128, 0)),
+            ("#FF8000", Colour::from_u8(255, 128, 0)),
+            ("  #ff800080 ", Colour::from_u8_rgba(255, 128, 0, 128)),
+            ("#f80", Colour::from_u8(255, 136, 0)),
+            ("#f808", Colour::from_u8_rgba(255, 136, 0, 136)),
+            ("#000", Colour::grey(0.0)),
+            ("#ffffff00", Colour::grey(1.0).with_alpha(0.0)),
+        ];
+        for (hex, expected) in known {
+            assert_eq!(Colour::from_hex(hex).unwrap(), expected, "{hex}");
+        }
+        for invalid in [
+            "ff8000", "#", "#ff80000", "#ff800g", "#+f8000", "# ff8000", "#ff80€",
+        ] {
+            assert!(Colour::from_hex(invalid).is_err(), "{invalid}");
+        }
+        assert_eq!(
+            Colour::from_hex("#12345").unwrap_err(),
+            "`#12345` has 5 hex digits, expected 3, 4, 6 or 8."
+        );
+    }
+
+    #[test]
+    pub fn from_css_test() {
+        let orange = Colour::from_u8(255, 128, 0);
+        let known = [
+            ("rgb(255, 128, 0)", orange),
+            ("rgb(255,128,0)", orange),
+            ("  RGB( 255 , 128 , 0 )  ", orange),
+            ("rgba(255,128,0,0.5)", orange.with_alpha(0.5)),
+            ("rgba(255, 128, 0, 25%)", orange.with_alpha(0.25)),
+            ("rgb(255, 128, 0, 0)", orange.with_alpha(0.0)),
+            ("rgba(255, 128, 0)", orange),
+            ("#ff8000", orange),
+            (" #FF8000 ", orange),
+            ("CornflowerBlue", Colour::from_u8(100, 149, 237)),
+            ("transparent", Colour::transparent()),
+        ];
+        for (css, expected) in known {
+            assert_eq!(Colour::from_css(css).unwrap(), expected, "{css}");
+        }
+        assert_relative_eq!(
+            Colour::from_css("oklch(62.8% 0.2577 29.23)").unwrap(),
+            Colour::red(1.0),
+            epsilon = 1e-3
+        );
+
+        for invalid in [
+            "",
+            "notacolour",
+            "rgb(255, 128)",
+            "rgb(255, 128, 0, 1, 1)",
+            "rgb(256, 0, 0)",
+            "rgb(-1, 0, 0)",
+            "rgb(0.5, 0, 0)",
+            "rgb(255, 128, 0",
+            "rgba(255, 128, 0, half)",
+            "hsl(0, 100%, 50%)",
+            "#ff80000",
+        ] {
+            assert!(Colour::from_css(invalid).is_err(), "{invalid}");
+        }
+        assert_eq!(
+            Colour::from_css("rgb(255, 300, 0)").unwrap_err(),
+            "`300` is not an integer between 0 and 255."
+        );
+        assert_eq!(
+            Colour::from_css("rgb(255, 128)").unwrap_err(),
+            "Expected 3 or 4 comma separated channels but found 2."
+        );
+        assert_eq!(
+            Colour::from_css("bluish").unwrap_err(),
+            "`bluish` is not a CSS colour name, hex colour or function."
+        );
+    }
+
+    #[test]
+    pub fn transfer_function_test() {
+        assert_relative_eq!(Colour::grey(0.5).to_srgb().r, 0.735, epsilon = 1e-3);
+        assert_relative_eq!(Colour::grey(0.735).to_linear().r, 0.5, epsilon = 1e-3);
+
+        // The ends and alpha are untouched, the linear toe is respected
+        let colour = Colour::new(0.0, 1.0, 0.002, 0.25);
+        assert_relative_eq!(
+            colour.to_linear(),
+            Colour::new(0.0, 1.0, 0.002 / 12.92, 0.25)
+        );
+        assert_relative_eq!(colour.to_srgb(), Colour::new(0.0, 1.0, 0.002 * 12.92, 0.25));
+
+        for i in 0..=100 {
+            let v = i as f64 / 100.0;
+            let colour = Colour::new(v, 1.0 - v, (v * 7.0) % 1.0, v);
+            assert_relative_eq!(colour.to_linear().to_srgb(), colour, epsilon = 1e-12);
+            assert_relative_eq!(colour.to_srgb().to_linear(), colour, epsilon = 1e-12);
+            // Decoding darkens everything but the ends
+            assert!(
+                colour
+                    .to_linear()
+                    .all_with(colour, |linear, srgb| linear <= srgb)
+            );
+        }
+    }
+
+    #[test]
+    pub fn lighter_darker_test() {
+        let (dark, light) = (Colour::solid(0.1, 0.2, 0.3), Colour::solid(0.9, 0.8, 0.7));
+        assert_eq!(dark.lighter_of(light), light);
+        assert_eq!(light.lighter_of(dark), light);
+        assert_eq!(dark.darker_of(light), dark);
+        assert_eq!(light.darker_of(dark), dark);
+
+        // Whole colours are picked, rather than channel by channel like Lighten
+        let (red, green) = (Colour::red(1.0), Colour::green(0.6));
+        assert_eq!(red.lighter_of(green), green);
+        assert_eq!(red.darker_of(green), red);
+        assert_eq!(
+            red.blend(green, BlendMode::Lighten),
+            Colour::solid(1.0, 0.6, 0.0)
+        );
+
+        // Ties keep self, alpha doesn't matter
+        let translucent = Colour::grey(0.5).with_alpha(0.1);
+        assert_eq!(translucent.lighter_of(Colour::grey(0.5)), translucent);
+        assert_eq!(translucent.darker_of(Colour::grey(0.5)), translucent);
+    }
+
+    #[test]
+    pub fn ensure_contrast_test() {
+        let (black, white) = (Colour::grey(0.0), Colour::grey(1.0));
+        // White only reaches 3.97:1 against mid grey so it has to darken
+        let grey = Colour::grey(0.5);
+        let adjusted = grey.ensure_contrast(&grey, 4.5);
+        assert!(adjusted.contrast_ratio(&grey) >= 4.5);
+        assert_relative_eq!(adjusted.contrast_ratio(&grey), 4.5, epsilon = 1e-9);
+        assert!(adjusted.luminance() < grey.luminance());
+        assert_relative_eq!(adjusted.r, adjusted.g);
+        assert_relative_eq!(adjusted.g, adjusted.b);
+
+        // Lighter colours lighten as long as they can get there
+        let background = Colour::grey(0.2);
+        let light = Colour::new(0.6, 0.3, 0.3, 0.5).ensure_contrast(&background, 7.0);
+        assert_relative_eq!(light.contrast_ratio(&background), 7.0, epsilon = 1e-9);
+        assert!(light.luminance() > Colour::solid(0.6, 0.3, 0.3).luminance());
+        let ((h, s, _), (light_h, light_s, _)) =
+            (Colour::solid(0.6, 0.3, 0.3).to_hsl(), light.to_hsl());
+        assert_relative_eq!(light_h, h, epsilon = 1e-9);
+        assert_relative_eq!(light_s, s, epsilon = 1e-9);
+        assert_eq!(light.a, 0.5);
+
+        // Already enough contrast, nothing changes
+        let dark = Colour::solid(0.1, 0.2, 0.3);
+        assert_eq!(dark.ensure_contrast(&white, 4.5), dark);
+        assert_eq!(black.ensure_contrast(&white, 21.0), black);
+
+        // Impossible targets saturate at whichever extreme is better
+        assert_eq!(grey.ensure_contrast(&grey, 21.0), black);
+        assert_eq!(
+            Colour::grey(0.3).ensure_contrast(&Colour::grey(0.8), 21.0),
+            black
+        );
+        assert_eq!(
+            Colour::grey(0.5).ensure_contrast(&Colour::grey(0.2), 21.0),
+            white
+        );
     }
-}
-impl From<(u8, u8, u8)> for Colour {
-    fn from((r, g, b): (u8, u8, u8)) -> Self {
-        Colour::from_u8(r, g, b)
+
+    #[test]
+    pub fn contrast_test() {
+        let (black, white) = (Colour::grey(0.0), Colour::grey(1.0));
+        assert_relative_eq!(black.luminance(), 0.0);
+        assert_relative_eq!(white.luminance(), 1.0);
+        assert_relative_eq!(black.contrast_ratio(&white), 21.0);
+        assert_relative_eq!(white.contrast_ratio(&black), 21.0);
+
+        for colour in [black, white, Colour::red(1.0), Colour::solid(0.2, 0.4, 0.6)] {
+            assert_relative_eq!(colour.contrast_ratio(&colour), 1.0);
+        }
+        // Alpha is ignored
+        assert_relative_eq!(white.with_alpha(0.0).contrast_ratio(&white), 1.0);
+
+        // Mid grey is far darker than 0.5 luminance once linearised
+        assert_relative_eq!(
+            Colour::grey(0.5).luminance(),
+            0.21404114048223255,
+            epsilon = 1e-12
+        );
+        assert_relative_eq!(
+            Colour::from_u8(0x76, 0x76, 0x76).contrast_ratio(&white),
+            4.54,
+            epsilon = 1e-2
+        );
     }
-}
-impl From<(u8, u8, u8, u8)> for Colour {
-    fn from((r, g, b, a): (u8, u8, u8, u8)) -> Self {
-        Colour::from_u8_rgba(r, g, b, a)
+
+    #[test]
+    pub fn composite_stack_test() {
+        let naive = |layers: &[Colour]| {
+            layers[1..]
+                .iter()
+                .fold(layers[0], |base, &layer| base.compose(layer))
+        };
+
+        let translucent = [
+            Colour::new(0.8, 0.4, 0.2, 0.7),
+            Colour::new(0.2, 0.6, 1.0, 0.4),
+            Colour::grey(0.5).with_alpha(0.5),
+            Colour::red(1.0).with_alpha(0.1),
+        ];
+        assert_relative_eq!(Colour::composite_stack(&translucent), naive(&translucent));
+        assert_relative_eq!(Colour::composite_stack(&translucent[..1]), translucent[0]);
+        assert_eq!(Colour::composite_stack(&[]), Colour::transparent());
+
+        // An opaque middle layer hides everything under it
+        let mut layers = translucent;
+        layers[1] = Colour::solid(0.2, 0.6, 1.0);
+        let composited = Colour::composite_stack(&layers);
+        assert_relative_eq!(composited, naive(&layers));
+        assert_relative_eq!(composited, naive(&layers[1..]));
+        layers[0] = Colour::green(1.0);
+        assert_relative_eq!(Colour::composite_stack(&layers), composited);
+        // ...even one with NaN channels
+        layers[0] = Colour::grey(f64::NAN);
+        assert_relative_eq!(Colour::composite_stack(&layers), composited);
     }
-}
 
-impl TryFrom<&[f64]> for Colour {
-    type Error = String;
-    fn try_from(value: &[f64]) -> Result<Self, Self::Error> {
-        if value.len() > 4 {
-            Err("There are too many elements.".to_string())
-        } else if value.len() < 3 {
-            Err("There are not enough elements.".to_string())
-        } else if value.len() == 3 {
-            Ok(Colour::solid(value[0], value[1], value[2]))
-        } else {
-            Ok(Colour::new(value[0], value[1], value[2], value[3]))
+    #[test]
+    pub fn gamma_test() {
+        let colour = Colour::new(0.8, 0.3, 0.6, 0.4);
+        assert_eq!(colour.gamma(1.0), colour);
+        assert_relative_eq!(colour.gamma(2.0), Colour::new(0.64, 0.09, 0.36, 0.4));
+        assert_relative_eq!(colour.gamma(2.0).gamma(0.5), colour, epsilon = 1e-12);
+        // Black and white are fixed points
+        for power in [0.25, 1.0 / 2.2, 2.2, 4.0] {
+            assert_eq!(Colour::grey(0.0).gamma(power), Colour::grey(0.0));
+            assert_eq!(Colour::grey(1.0).gamma(power), Colour::grey(1.0));
         }
+        // Close to, but not the same as, the sRGB transfer function
+        let encoded = Colour::grey(0.5).gamma(1.0 / 2.2);
+        assert_relative_eq!(encoded, Colour::grey(0.5).to_srgb(), epsilon = 0.02);
+        assert_ne!(encoded, Colour::grey(0.5).to_srgb());
+
+        let mut applied = colour;
+        applied.apply_gamma(2.0);
+        assert_eq!(applied, colour.gamma(2.0));
     }
-}
 
-impl TryFrom<Vec<f64>> for Colour {
-    type Error = String;
-    fn try_from(value: Vec<f64>) -> Result<Self, Self::Error> {
-        if value.len() > 4 {
-            Err("There are too many elements.".to_string())
-        } else if value.len() < 3 {
-            Err("There are not enough elements.".to_string())
-        } else if value.len() == 3 {
-            Ok(Colour::solid(value[0], value[1], value[2]))
-        } else {
-            Ok(Colour::new(value[0], value[1], value[2], value[3]))
-        }
+    #[test]
+    pub fn luminance_linear_test() {
+        // A linear mid-grey is half the light, the same grey as sRGB is less
+        let grey = Colour::grey(0.5);
+        assert_relative_eq!(grey.luminance_linear(), 0.5);
+        assert_relative_eq!(grey.luminance(), 0.214041140482232, epsilon = 1e-12);
+        assert_relative_eq!(grey.to_linear().luminance_linear(), grey.luminance());
+        // The same weights as luma, only the expected encoding differs
+        let colour = Colour::solid(0.2, 0.4, 0.6);
+        assert_eq!(colour.luminance_linear(), colour.luma());
+        assert_eq!(colour.luminance(), colour.to_linear().luma());
+        assert_relative_eq!(Colour::green(1.0).luminance_linear(), 0.7152);
+        assert_relative_eq!(Colour::grey(1.0).luminance_linear(), 1.0);
     }
-}
 
-impl Into<[f64; 4]> for Colour {
-    fn into(self) -> [f64; 4] {
-        [self.r, self.g, self.b, self.a]
+    #[test]
+    pub fn luma_test() {
+        assert_relative_eq!(Colour::red(1.0).luma(), 0.2126);
+        assert_relative_eq!(Colour::green(1.0).luma(), 0.7152);
+        assert_relative_eq!(Colour::blue(1.0).luma(), 0.0722);
+        assert_relative_eq!(Colour::grey(1.0).luma(), 1.0);
+
+        for colour in [
+            Colour::solid(0.2, 0.4, 0.6),
+            Colour::new(0.9, 0.1, 0.3, 0.25),
+            Colour::grey(0.5),
+        ] {
+            let grey = colour.grayscale();
+            assert_eq!(grey.r, grey.g);
+            assert_eq!(grey.g, grey.b);
+            assert_eq!(grey.r, colour.luma());
+            assert_eq!(grey.a, colour.a);
+        }
+        // Greys are unchanged
+        assert_relative_eq!(Colour::grey(0.3).grayscale(), Colour::grey(0.3));
     }
-}
-impl Into<(f64, f64, f64, f64)> for Colour {
-    fn into(self) -> (f64, f64, f64, f64) {
-        (self.r, self.g, self.b, self.a)
+
+    #[test]
+    pub fn luma_to_alpha_test() {
+        let (white, black) = (Colour::grey(1.0), Colour::grey(0.0).with_alpha(0.5));
+        assert_eq!(white.luma_to_alpha(false), white);
+        assert_eq!(black.luma_to_alpha(false), Colour::transparent());
+        assert_eq!(white.luma_to_alpha(true), white.with_alpha(0.0));
+        assert_eq!(black.luma_to_alpha(true), Colour::grey(0.0));
+
+        let colour = Colour::new(0.2, 0.4, 0.6, 0.1);
+        let keyed = colour.luma_to_alpha(false);
+        assert_eq!(keyed.with_alpha(colour.a), colour);
+        assert_relative_eq!(keyed.a, colour.luma());
+        assert_relative_eq!(colour.luma_to_alpha(true).a, 1.0 - colour.luma());
     }
-}
-impl Into<Vec<f64>> for Colour {
-    fn into(self) -> Vec<f64> {
-        Vec::from([self.r, self.g, self.b, self.a])
+
+    #[test]
+    pub fn saturation_amount_test() {
+        let red = Colour::red(1.0).with_alpha(0.5);
+        let half = red.desaturated(0.5);
+        // Every channel moved halfway to the luma grey
+        assert_relative_eq!(half, Colour::new(0.6063, 0.1063, 0.1063, 0.5));
+        assert_relative_eq!(red.desaturated(1.0), red.grayscale());
+        assert_eq!(red.desaturated(0.0), red);
+
+        let colour = Colour::new(0.6, 0.4, 0.3, 0.25);
+        assert_eq!(colour.saturated(0.0), colour);
+        assert_relative_eq!(
+            colour.saturated(1.0),
+            Colour::new(0.7647, 0.3647, 0.1647, 0.25)
+        );
+        // The luma is kept by both
+        assert_relative_eq!(colour.saturated(0.3).luma(), colour.luma());
+        assert_relative_eq!(colour.desaturated(0.3).luma(), colour.luma());
+        // Greys have nothing to move
+        assert_relative_eq!(Colour::grey(0.4).saturated(1.0), Colour::grey(0.4));
     }
-}
-impl Into<[u8; 4]> for Colour {
-    fn into(self) -> [u8; 4] {
-        let (r, g, b, a) = self.as_u8_rgba();
-        [r, g, b, a]
+
+    #[test]
+    pub fn fold_test() {
+        let colour = Colour::new(0.1, 0.2, 0.3, 0.4);
+        assert_relative_eq!(colour.fold(0.0, |sum, v| sum + v * v), 0.14);
+        assert_relative_eq!(colour.fold_rgba(0.0, |sum, v| sum + v * v), 0.3);
+        assert_eq!(colour.fold(f64::MIN, f64::max), 0.3);
+        assert_eq!(colour.fold_rgba(f64::MIN, f64::max), 0.4);
+        // Channels are visited in order
+        let order = |acc: (u32, bool), v: f64| (acc.0 + 1, acc.1 && v == (acc.0 + 1) as f64 / 10.0);
+        assert_eq!(colour.fold((0, true), order), (3, true));
+        assert_eq!(colour.fold_rgba((0, true), order), (4, true));
     }
-}
-impl Into<(u8, u8, u8, u8)> for Colour {
-    fn into(self) -> (u8, u8, u8, u8) {
-        self.as_u8_rgba()
+
+    #[test]
+    pub fn rgb_sum_test() {
+        assert_eq!(Colour::grey(1.0).rgb_sum(), 3.0);
+        assert_eq!(Colour::grey(1.0).rgb_mean(), 1.0);
+        assert_eq!(Colour::transparent().rgb_sum(), 0.0);
+        // Alpha doesn't count
+        let colour = Colour::new(0.3, 0.6, 0.9, 0.5);
+        assert_relative_eq!(colour.rgb_sum(), 1.8);
+        assert_relative_eq!(colour.rgb_mean(), 0.6);
+        // Unweighted, so every primary is as intense as the others
+        assert_eq!(Colour::red(1.0).rgb_mean(), Colour::blue(1.0).rgb_mean());
     }
-}
-impl Into<Vec<u8>> for Colour {
-    fn into(self) -> Vec<u8> {
-        let (r, g, b, a) = self.as_u8_rgba();
-        Vec::from([r, g, b, a])
+
+    #[test]
+    pub fn distance_test() {
+        let (black, white) = (Colour::grey(0.0), Colour::grey(1.0));
+        assert_relative_eq!(black.distance(&white), 3f64.sqrt());
+        assert_relative_eq!(black.manhattan(&white), 3.0);
+        assert_relative_eq!(black.distance_rgba(&white), 3f64.sqrt());
+        assert_relative_eq!(Colour::transparent().distance_rgba(&white), 2.0);
+
+        let (from, to) = (
+            Colour::new(0.2, 0.4, 0.6, 0.1),
+            Colour::new(0.5, 0.0, 0.6, 0.9),
+        );
+        for colour in [black, white, from, to] {
+            assert_eq!(colour.distance(&colour), 0.0);
+            assert_eq!(colour.distance_rgba(&colour), 0.0);
+            assert_eq!(colour.manhattan(&colour), 0.0);
+        }
+        assert_relative_eq!(from.distance(&to), 0.5);
+        assert_relative_eq!(to.distance(&from), 0.5);
+        assert_relative_eq!(from.manhattan(&to), 0.7);
+        assert_relative_eq!(from.distance_rgba(&to), 0.89f64.sqrt());
+        // Alpha only counts for distance_rgba
+        assert_eq!(from.distance(&from.with_alpha(1.0)), 0.0);
     }
-}
-#[cfg(feature = "ratatui")]
-impl From<ratatui::style::Color> for Colour {
-    fn from(colour: ratatui::style::Color) -> Colour {
-        match colour {
-            ratatui::style::Color::Black => Colour::from_u8(0, 0, 0),
-            ratatui::style::Color::Red => Colour::from_u8(255, 0, 0),
-            ratatui::style::Color::Green => Colour::from_u8(0, 255, 0),
-            ratatui::style::Color::Yellow => Colour::from_u8(255, 255, 0),
-            ratatui::style::Color::Blue => Colour::from_u8(0, 0, 255),
-            ratatui::style::Color::Magenta => Colour::from_u8(255, 0, 255),
-            ratatui::style::Color::Cyan => Colour::from_u8(0, 255, 255),
-            ratatui::style::Color::Gray => Colour::from_u8(169, 169, 169),
-            ratatui::style::Color::DarkGray => Colour::from_u8(128, 128, 128),
-            ratatui::style::Color::LightRed => Colour::from_u8(255, 128, 128),
-            ratatui::style::Color::LightGreen => Colour::from_u8(128, 255, 128),
-            ratatui::style::Color::LightYellow => Colour::from_u8(255, 255, 128),
-            ratatui::style::Color::LightBlue => Colour::from_u8(128, 128, 255),
-            ratatui::style::Color::LightMagenta => Colour::from_u8(255, 128, 255),
-            ratatui::style::Color::LightCyan => Colour::from_u8(128, 255, 255),
-            ratatui::style::Color::White => Colour::from_u8(255, 255, 255),
-            ratatui::style::Color::Rgb(r, g, b) => Colour::from_u8(r, g, b),
-            ratatui::style::Color::Indexed(index) => {
-                if index <= 6 {
-                    Colour::from_u8(
-                        (index & 0b100) * 0b11111111,
-                        (index & 0b010) * 0b11111111,
-                        (index & 0b001) * 0b11111111,
-                    )
-                } else if index == 7 {
-                    Colour::from_u8(169, 169, 169)
-                } else if index <= 15 {
-                    Colour::from_u8(
-                        (index & 0b100) * 0b01111111 + 0b10000000,
-                        (index & 0b010) * 0b01111111 + 0b10000000,
-                        (index & 0b001) * 0b01111111 + 0b10000000,
-                    )
-                } else if index < 232 {
-                    // 6x6x6 color cube
-                    let index = index - 16;
-                    let r = (index / 36) * 51;
-                    let g = ((index % 36) / 6) * 51;
-                    let b = (index % 6) * 51;
-                    return Colour::from_u8(r, g, b);
-                } else {
-                    // Grayscale ramp (232-255)
-                    let gray = 8 + (index - 232) * 10;
-                    return Colour::from_u8(gray, gray, gray);
-                }
-            }
-            _ => Colour::from_u8(0, 0, 0), // Default case for unknown colors
+
+    #[test]
+    pub fn random_test() {
+        use rand::{Rng, SeedableRng, rngs::StdRng};
+
+        let colours = |seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            (0..100)
+                .map(|_| (Colour::random(&mut rng), Colour::random_rgba(&mut rng)))
+                .collect::<Vec<_>>()
+        };
+        // The same seed gives the same colours
+        assert_eq!(colours(0xbeef), colours(0xbeef));
+        assert_ne!(colours(0xbeef), colours(0xdead));
+        for (solid, rgba) in colours(0xbeef) {
+            assert_eq!(solid.a, 1.0);
+            assert!(solid.all_rgba(|v| (0.0..=1.0).contains(&v)));
+            assert!(rgba.all_rgba(|v| (0.0..=1.0).contains(&v)));
         }
+        assert!(colours(0xbeef).iter().any(|(_, rgba)| rgba.a < 1.0));
+
+        let (mut a, mut b) = (StdRng::seed_from_u64(7), StdRng::seed_from_u64(7));
+        assert_eq!(a.random::<Colour>(), Colour::random(&mut b));
     }
-}
 
-#[cfg(feature = "ratatui")]
-impl Into<ratatui::style::Color> for Colour {
-    fn into(self) -> ratatui::style::Color {
-        let (r, g, b) = self.as_u8();
-        ratatui::style::Color::Rgb(r, g, b)
+    #[test]
+    pub fn cmyk_test() {
+        assert_eq!(Colour::grey(0.0).to_cmyk(), (0.0, 0.0, 0.0, 1.0));
+        assert_eq!(Colour::red(1.0).to_cmyk(), (0.0, 1.0, 1.0, 0.0));
+        assert_eq!(Colour::grey(1.0).to_cmyk(), (0.0, 0.0, 0.0, 0.0));
+        assert_eq!(Colour::solid(0.0, 0.5, 1.0).to_cmyk(), (1.0, 0.5, 0.0, 0.0));
+        assert_eq!(Colour::from_cmyk(0.0, 0.0, 0.0, 1.0), Colour::grey(0.0));
+        assert_eq!(Colour::from_cmyk(0.0, 1.0, 1.0, 0.0), Colour::red(1.0));
+
+        for colour in [
+            Colour::solid(0.2, 0.4, 0.6),
+            Colour::grey(0.5),
+            Colour::new(0.9, 0.1, 0.3, 0.25),
+        ] {
+            let (c, m, y, k) = colour.to_cmyk();
+            assert_relative_eq!(
+                Colour::from_cmyk(c, m, y, k).with_alpha(colour.a),
+                colour,
+                epsilon = 1e-12
+            );
+            // Alpha survives the round trip with `cmyka`
+            let (c, m, y, k, a) = colour.to_cmyka();
+            assert_eq!(a, colour.a);
+            assert_eq!(Colour::from_cmyk(c, m, y, k).a, 1.0);
+            assert_relative_eq!(Colour::from_cmyka(c, m, y, k, a), colour, epsilon = 1e-12);
+        }
+        assert_eq!(Colour::transparent().to_cmyka(), (0.0, 0.0, 0.0, 1.0, 0.0));
     }
-}
 
-#[cfg(feature = "ratatui")]
-impl Into<ratatui::style::Color> for &Colour {
-    fn into(self) -> ratatui::style::Color {
-        let (r, g, b) = self.as_u8();
-        ratatui::style::Color::Rgb(r, g, b)
+    #[test]
+    pub fn hsl_test() {
+        let known = [
+            (Colour::red(1.0), (0.0, 1.0, 0.5)),
+            (Colour::green(1.0), (120.0, 1.0, 0.5)),
+            (Colour::blue(1.0), (240.0, 1.0, 0.5)),
+            (Colour::solid(1.0, 0.0, 1.0), (300.0, 1.0, 0.5)),
+            (Colour::solid(0.5, 0.25, 0.25), (0.0, 1.0 / 3.0, 0.375)),
+            (Colour::grey(0.3), (0.0, 0.0, 0.3)),
+        ];
+        for (colour, (h, s, l)) in known {
+            let hsl = colour.to_hsl();
+            assert_relative_eq!(hsl.0, h, epsilon = 1e-12);
+            assert_relative_eq!(hsl.1, s, epsilon = 1e-12);
+            assert_relative_eq!(hsl.2, l, epsilon = 1e-12);
+            assert_relative_eq!(Colour::from_hsl(h, s, l), colour, epsilon = 1e-12);
+        }
+        assert_relative_eq!(Colour::from_hsl(-240.0, 1.0, 0.5), Colour::green(1.0));
     }
-}
 
-#[cfg(feature = "nalgebra")]
-impl From<nalgebra::Vector4<f64>> for Colour {
-    fn from(value: nalgebra::Vector4<f64>) -> Colour {
-        Colour::new(value.x, value.y, value.z, value.w)
+    #[test]
+    pub fn rotate_hue_test() {
+        let red = Colour::red(1.0).with_alpha(0.5);
+        assert_relative_eq!(red.rotate_hue(120.0), Colour::green(1.0).with_alpha(0.5));
+        assert_relative_eq!(red.rotate_hue(240.0), Colour::blue(1.0).with_alpha(0.5));
+        assert_relative_eq!(red.rotate_hue(180.0), Colour::new(0.0, 1.0, 1.0, 0.5));
+        assert_relative_eq!(red.rotate_hue(-120.0), red.rotate_hue(240.0));
+        assert_relative_eq!(red.rotate_hue(720.0), red);
+
+        let colour = Colour::solid(0.8, 0.2, 0.4);
+        let (h, s, l) = colour.to_hsl();
+        let (rotated_h, rotated_s, rotated_l) = colour.rotate_hue(90.0).to_hsl();
+        assert_relative_eq!(rotated_h, (h + 90.0) % 360.0, epsilon = 1e-9);
+        assert_relative_eq!(rotated_s, s, epsilon = 1e-9);
+        assert_relative_eq!(rotated_l, l, epsilon = 1e-9);
+
+        for grey in [0.0, 0.3, 1.0] {
+            let grey = Colour::grey(grey).with_alpha(0.25);
+            assert_eq!(grey.rotate_hue(137.0), grey);
+        }
     }
-}
 
-#[cfg(feature = "nalgebra")]
-impl Into<nalgebra::Vector4<f64>> for Colour {
-    fn into(self) -> nalgebra::Vector4<f64> {
-        nalgebra::Vector4::new(self.r, self.g, self.b, self.a)
+    #[test]
+    pub fn with_hsl_test() {
+        let red = Colour::red(1.0).with_alpha(0.5);
+        assert_relative_eq!(red.with_hue(120.0), Colour::green(1.0).with_alpha(0.5));
+        assert_relative_eq!(red.with_hue(-120.0), Colour::blue(1.0).with_alpha(0.5));
+        assert_relative_eq!(red.with_saturation(0.0), Colour::grey(0.5).with_alpha(0.5));
+        assert_relative_eq!(red.with_lightness(1.0), Colour::grey(1.0).with_alpha(0.5));
+        assert_relative_eq!(red.with_lightness(0.25), Colour::new(0.5, 0.0, 0.0, 0.5));
+
+        let colour = Colour::solid(0.8, 0.2, 0.4);
+        let (h, s, l) = colour.to_hsl();
+        for (set, expected) in [
+            (colour.with_hue(200.0), (200.0, s, l)),
+            (colour.with_saturation(0.3), (h, 0.3, l)),
+            (colour.with_lightness(0.7), (h, s, 0.7)),
+        ] {
+            let hsl = set.to_hsl();
+            assert_relative_eq!(hsl.0, expected.0, epsilon = 1e-9);
+            assert_relative_eq!(hsl.1, expected.1, epsilon = 1e-9);
+            assert_relative_eq!(hsl.2, expected.2, epsilon = 1e-9);
+        }
+        // Setting a component to what it already is changes nothing
+        assert_relative_eq!(colour.with_hue(h), colour, epsilon = 1e-12);
+        assert_relative_eq!(colour.with_lightness(l), colour, epsilon = 1e-12);
+
+        let grey = Colour::grey(0.3);
+        assert_relative_eq!(grey.with_hue(90.0), grey);
     }
-}
 
-#[cfg(test)]
-mod tests {
+    #[test]
+    pub fn lightened_test() {
+        assert_relative_eq!(Colour::grey(0.0).lightened(0.5), Colour::grey(0.5));
+        assert_relative_eq!(Colour::grey(1.0).darkened(0.5), Colour::grey(0.5));
+        assert_relative_eq!(Colour::grey(0.8).lightened(0.5), Colour::grey(1.0));
+        assert_relative_eq!(Colour::grey(0.2).darkened(0.5), Colour::grey(0.0));
 
-    use approx::assert_relative_eq;
-    use crate::{BlendMode, Colour};
-    
-    #[cfg(feature="image-tests")]
-    use color_eyre::eyre::Result;
+        let colour = Colour::new(0.8, 0.2, 0.4, 0.5);
+        let (h, s, l) = colour.to_hsl();
+        for amount in [0.1, 0.2, 0.3] {
+            let lighter = colour.lightened(amount);
+            let darker = colour.darkened(amount);
+            assert_relative_eq!(lighter.to_hsl().0, h, epsilon = 1e-9);
+            assert_relative_eq!(darker.to_hsl().0, h, epsilon = 1e-9);
+            assert_relative_eq!(lighter.to_hsl().1, s, epsilon = 1e-9);
+            assert_relative_eq!(lighter.to_hsl().2, l + amount, epsilon = 1e-9);
+            assert_relative_eq!(darker.to_hsl().2, l - amount, epsilon = 1e-9);
+            assert_eq!(lighter.a, 0.5);
+        }
+        assert_relative_eq!(colour.lightened(0.0), colour, epsilon = 1e-12);
+    }
 
     #[test]
-    pub fn ops_test() {
-        let a = Colour::red(1.0);
-        let b = Colour::grey(0.5);
-        assert_relative_eq!(a * b, Colour::red(0.5));
-        assert_relative_eq!(a + b, Colour::solid(1.5, 0.5, 0.5));
-        assert_relative_eq!(a - b, Colour::solid(0.5, -0.5, -0.5));
-        assert_relative_eq!(2f64 * b, Colour::grey(1.0));
-        let c = Colour::grey(1.0);
-        let d = Colour::transparent();
-        assert_relative_eq!(d - c, Colour::grey(-1.0).with_alpha(0.0));
+    pub fn delta_e_test() {
+        use super::ciede2000;
+
+        // Pairs 1, 7, 17 and 25 of Sharma, Wu and Dalal's test data
+        let pairs = [
+            ((50.0, 2.6772, -79.7751), (50.0, 0.0, -82.7485), 2.0425),
+            ((50.0, 0.0, 0.0), (50.0, -1.0, 2.0), 2.3669),
+            ((50.0, 2.5, 0.0), (73.0, 25.0, -18.0), 27.1492),
+            (
+                (60.2574, -34.0099, 36.2677),
+                (60.4626, -34.1751, 39.4387),
+                1.2644,
+            ),
+        ];
+        for (lab1, lab2, delta_e) in pairs {
+            assert_relative_eq!(ciede2000(lab1, lab2), delta_e, epsilon = 1e-4);
+            assert_relative_eq!(ciede2000(lab2, lab1), delta_e, epsilon = 1e-4);
+        }
+
+        // Through sRGB, white is L = 100 with no chroma
+        let (l, a, b) = Colour::grey(1.0).to_lab();
+        assert_relative_eq!(l, 100.0, epsilon = 1e-9);
+        assert_relative_eq!(a, 0.0, epsilon = 1e-9);
+        assert_relative_eq!(b, 0.0, epsilon = 1e-9);
+        assert_relative_eq!(
+            Colour::grey(0.0).delta_e(&Colour::grey(1.0)),
+            100.0,
+            epsilon = 1e-9
+        );
+        let colour = Colour::solid(0.2, 0.4, 0.6);
+        assert_eq!(colour.delta_e(&colour), 0.0);
+        // Alpha is ignored
+        assert_eq!(colour.delta_e(&colour.with_alpha(0.0)), 0.0);
     }
 
     #[test]
-    pub fn blend_normality_test() {
+    pub fn hsluv_test() {
+        // (rgb, HSLuv) from the reference implementation's snapshot
+        let snapshot = [
+            (
+                (255u8, 0u8, 0u8),
+                (12.177050630061776, 100.0, 53.23711559542933),
+            ),
+            ((0, 255, 0), (127.71501294924046, 100.0, 87.73551910966002)),
+            ((0, 0, 255), (265.8743202181779, 100.0, 32.30087290398002)),
+        ];
+        for ((r, g, b), (h, s, l)) in snapshot {
+            let colour = Colour::from_u8(r, g, b);
+            let hsluv = colour.to_hsluv();
+            assert_relative_eq!(hsluv.0, h, epsilon = 1e-6);
+            assert_relative_eq!(hsluv.1, s, epsilon = 1e-6);
+            assert_relative_eq!(hsluv.2, l, epsilon = 1e-6);
+            assert_relative_eq!(Colour::from_hsluv(h, s, l), colour, epsilon = 1e-9);
+        }
+        let (h, s, l) = Colour::red(1.0).to_hpluv();
+        assert_relative_eq!(h, 12.177050630061776, epsilon = 1e-6);
+        assert_relative_eq!(s, 426.7467891831313, epsilon = 1e-6);
+        assert_relative_eq!(l, 53.23711559542933, epsilon = 1e-6);
 
-        use strum::IntoEnumIterator;
+        // HPLuv saturation is hue independent, 100 is in gamut for every hue
+        for i in 0..36 {
+            let colour = Colour::from_hpluv(i as f64 * 10.0, 100.0, 60.0);
+            assert!(
+                colour.all(|v| (-1e-9..=1.0 + 1e-9).contains(&v)),
+                "{colour}"
+            );
+        }
 
-        for blend_mode in BlendMode::iter() {
-            for _ in 0..5000 {
-                let base = Colour::random();
-                let blend = Colour::random();
-                let blended = base.blend(blend, blend_mode);
+        // Greys have no saturation
+        for grey in [0.0, 0.25, 0.5, 1.0] {
+            let (_, s, l) = Colour::grey(grey).to_hsluv();
+            assert_relative_eq!(s, 0.0, epsilon = 1e-6);
+            assert_relative_eq!(
+                Colour::from_hsluv(0.0, 0.0, l),
+                Colour::grey(grey),
+                epsilon = 1e-9
+            );
+        }
 
-                // We expect these to lie outside of [0,1] and whether these are normalised or clamped
-                // is up to the user
-                if [BlendMode::Addition, BlendMode::Divide, BlendMode::Subtract]
-                    .contains(&blend_mode)
-                {
-                    assert!(
-                        blended.cleaned() == blended,
-                        "{:?}: ({:?} blends onto {:?}) = {:?}",
-                        blend_mode,
-                        blend,
-                        base,
-                        blended
-                    )
-                } else {
-                    assert!(
-                        blended.is_normal(),
-                        "{:?}: ({:?} blends onto {:?}) = {:?}",
-                        blend_mode,
-                        blend,
-                        base,
-                        blended
-                    )
+        // Round trips across the gamut
+        for r in 0..=8 {
+            for g in 0..=8 {
+                for b in 0..=8 {
+                    let colour = Colour::solid(r as f64 / 8.0, g as f64 / 8.0, b as f64 / 8.0);
+                    let (h, s, l) = colour.to_hsluv();
+                    assert_relative_eq!(Colour::from_hsluv(h, s, l), colour, epsilon = 1e-9);
+                    let (h, s, l) = colour.to_hpluv();
+                    assert_relative_eq!(Colour::from_hpluv(h, s, l), colour, epsilon = 1e-9);
                 }
             }
         }
     }
 
-    #[cfg(feature="image-tests")]
+    #[cfg(feature = "nalgebra")]
     #[test]
-    pub fn blend_visual_test() -> Result<()> {
-        use image::{DynamicImage, GenericImageView, ImageFormat, ImageReader, Rgba, RgbaImage};
+    pub fn nalgebra_test() {
+        use nalgebra::{Vector3, Vector4};
 
-        let cat = ImageReader::open("test/cat.jpg")?.decode()?;
-        let bricks = ImageReader::open("test/bricks.png")?.decode()?;
-        let get_colours = |image: &DynamicImage| -> Vec<Colour> {
-            image
-                .pixels()
-                .map(|(_, _, pixel)| {
-                    Colour::from_u8_rgba(pixel.0[0], pixel.0[1], pixel.0[2], pixel.0[3])
-                })
-                .collect()
-        };
+        let vector = Vector3::new(0.8, 0.4, 0.2);
+        let colour = Colour::from(vector);
+        assert_eq!(colour, Colour::solid(0.8, 0.4, 0.2));
+        assert_eq!(Into::<Vector3<f64>>::into(colour), vector);
+        // Alpha is dropped on the way out
+        assert_eq!(Into::<Vector3<f64>>::into(colour.with_alpha(0.5)), vector);
 
-        let brick_colours = get_colours(&bricks);
-        let mut cat_colours = get_colours(&cat);
-        cat_colours = cat_colours.iter().map(|f| f.with_alpha(1.0)).collect();
-        let mut blended_image = RgbaImage::new(512, 512);
+        let vector = Vector4::new(0.8, 0.4, 0.2, 0.5);
+        let colour = Colour::from(vector);
+        assert_eq!(colour, Colour::new(0.8, 0.4, 0.2, 0.5));
+        assert_eq!(Into::<Vector4<f64>>::into(colour), vector);
+    }
 
-        for (index, (base, blend)) in brick_colours.iter().zip(cat_colours).enumerate() {
-            let x = index as u32 % 512;
-            let y = index as u32 / 512;
-            blended_image.put_pixel(x, y, Rgba(base.blend(blend, BlendMode::Darken).into()));
+    #[cfg(feature = "glam")]
+    #[test]
+    pub fn glam_test() {
+        use glam::{Vec3, Vec4};
+
+        // Only as precise as `f32`
+        let colour = Colour::new(0.8, 0.4, 0.2, 0.6);
+        let vector: Vec4 = colour.into();
+        assert_eq!(vector, Vec4::new(0.8, 0.4, 0.2, 0.6));
+        assert_relative_eq!(Colour::from(vector), colour, epsilon = 1e-7);
+        let vector: Vec3 = colour.into();
+        assert_eq!(vector, Vec3::new(0.8, 0.4, 0.2));
+        assert_relative_eq!(Colour::from(vector), colour.with_alpha(1.0), epsilon = 1e-7);
+
+        for _ in 0..100 {
+            let colour = Colour::random_rgba(&mut rand::rng());
+            let vector: Vec4 = colour.into();
+            assert_relative_eq!(Colour::from(vector), colour, epsilon = 1e-7);
         }
-        blended_image.save_with_format("test/blended.png", ImageFormat::Png)?;
-        Ok(())
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    pub fn image_test() {
+        use image::{Rgb, Rgba};
+
+        let colour = Colour::new(0.8, 0.4, 0.2, 0.6);
+        let pixel: Rgba<u8> = colour.into();
+        assert_eq!(pixel, Rgba([204, 102, 51, 153]));
+        assert_relative_eq!(Colour::from(pixel), colour, epsilon = 1.0 / 255.0);
+        let pixel: Rgb<u8> = colour.into();
+        assert_eq!(pixel, Rgb([204, 102, 51]));
+
+        for _ in 0..100 {
+            let colour = Colour::random_rgba(&mut rand::rng());
+            let round_trip = Colour::from(Rgba::<u8>::from(colour));
+            assert_relative_eq!(round_trip, colour, epsilon = 1.0 / 255.0);
+        }
+        // Pixels survive the trip the other way exactly
+        for v in 0..=255 {
+            let pixel = Rgba([v, 255 - v, v / 2, v]);
+            assert_eq!(Rgba::<u8>::from(Colour::from(pixel)), pixel);
+        }
+    }
+
+    #[cfg(feature = "hash")]
+    #[test]
+    pub fn ordered_colour_test() {
+        use crate::OrderedColour;
+        use std::collections::{BTreeSet, HashMap};
+
+        let mut counts = HashMap::new();
+        for colour in [Colour::red(1.0), Colour::solid(1.0, 0.0, 0.0)] {
+            *counts.entry(OrderedColour(colour)).or_insert(0) += 1;
+        }
+        assert_eq!(counts.len(), 1);
+        assert_eq!(counts[&OrderedColour(Colour::red(1.0))], 2);
+
+        let mut counts = HashMap::new();
+        for colour in [Colour::red(1.0), Colour::red(1.0).with_alpha(0.5)] {
+            *counts.entry(OrderedColour(colour)).or_insert(0) += 1;
+        }
+        assert_eq!(counts.len(), 2);
+
+        // Exact bits, unlike `PartialEq` on the colour itself
+        assert_ne!(
+            OrderedColour(Colour::grey(0.0)),
+            OrderedColour(Colour::grey(-0.0))
+        );
+        let nan = Colour::grey(f64::NAN);
+        assert_eq!(OrderedColour(nan), OrderedColour(nan));
+        assert_eq!(
+            nan.to_bits(),
+            [
+                f64::NAN.to_bits(),
+                f64::NAN.to_bits(),
+                f64::NAN.to_bits(),
+                1f64.to_bits()
+            ]
+        );
+
+        let sorted: BTreeSet<OrderedColour> = [
+            Colour::blue(1.0),
+            Colour::red(1.0),
+            Colour::red(1.0).with_alpha(0.5),
+            Colour::grey(0.5),
+        ]
+        .map(OrderedColour)
+        .into();
+        let sorted: Vec<Colour> = sorted.into_iter().map(|colour| colour.0).collect();
+        assert_eq!(
+            sorted,
+            [
+                Colour::blue(1.0),
+                Colour::grey(0.5),
+                Colour::red(1.0).with_alpha(0.5),
+                Colour::red(1.0),
+            ]
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    pub fn serde_test() {
+        let colour = Colour::new(1.0, 0.5, 0.25, 0.75);
+        let json = serde_json::to_string(&colour).unwrap();
+        assert_eq!(json, "[1.0,0.5,0.25,0.75]");
+        assert_eq!(serde_json::from_str::<Colour>(&json).unwrap(), colour);
+
+        assert_eq!(
+            serde_json::from_str::<Colour>("[1.0, 0.5, 0.25]").unwrap(),
+            Colour::solid(1.0, 0.5, 0.25)
+        );
+        assert!(serde_json::from_str::<Colour>("[1.0, 0.5]").is_err());
+        assert!(serde_json::from_str::<Colour>("[1.0, 0.5, 0.25, 0.75, 1.0]").is_err());
     }
 
     #[cfg(feature = "deep-tests")]
@@ -1,11 +1,332 @@
-use std::ops::Not;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+#[cfg(feature = "approx")]
+use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+use core::cmp::Ordering;
+use core::fmt;
 
 use crate::colour::Colour;
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use crate::math::Float;
 
 pub type GradientStop = (f64, Colour);
+
+/// A list of `(t, colour)` stops, sorted by `t`.
+///
+/// Constructing it directly with `Gradient(stops)` assumes the
+/// stops are already sorted, sampling out of order stops gives wrong
+/// colours. `Gradient::new()` sorts them for you and `.sort()` fixes
+/// an existing gradient.
+///
+/// With the `serde` feature, a gradient (de)serializes as a list of
+/// `[t, [r, g, b, a]]` pairs.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Gradient(pub Vec<GradientStop>);
 
+/// Easing applied to the normalised `t` within a gradient segment
+/// before interpolating, see `Gradient::sample_eased()`.
+#[cfg_attr(test, derive(strum_macros::EnumIter))]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Easing {
+    /// `t`
+    Linear,
+    /// `t^2`, slow to leave the lower stop
+    EaseIn,
+    /// `1 - (1 - t)^2`, slow to reach the upper stop
+    EaseOut,
+    /// `EaseIn` for the first half of the segment and `EaseOut` for the second
+    EaseInOut,
+    /// `3t^2 - 2t^3`, flat at both stops
+    SmoothStep,
+}
+
+impl Easing {
+    /// Eases `t` in `[0, 1]`, every easing maps `0` to `0` and `1` to `1`.
+    pub fn apply(&self, t: f64) -> f64 {
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => 1f64 - (1f64 - t) * (1f64 - t),
+            Easing::EaseInOut => {
+                if t < 0.5f64 {
+                    2f64 * t * t
+                } else {
+                    1f64 - 2f64 * (1f64 - t) * (1f64 - t)
+                }
+            }
+            Easing::SmoothStep => t * t * (3f64 - 2f64 * t),
+        }
+    }
+}
+
+/// What `Gradient::sample_with_policy()` and `PolicyGradient::sample()`
+/// do with a `t` outside of the gradient's domain.
+#[cfg_attr(test, derive(strum_macros::EnumIter))]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum EdgePolicy {
+    /// Holds the colour of the nearest end stop, like `Gradient::sample()`
+    Clamp,
+    /// Continues the first or last segment's trend, clamping the colour
+    /// between `[0, 1]` like `Gradient::extend()`
+    Extrapolate,
+    /// Returns `Colour::transparent()`, useful for overlay gradients
+    Transparent,
+}
+
+/// How `Gradient::sample_wrapped()` brings a `t` outside of the
+/// gradient's domain back inside it.
+#[cfg_attr(test, derive(strum_macros::EnumIter))]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum WrapMode {
+    /// Holds the colour of the nearest end stop, like `Gradient::sample()`
+    Clamp,
+    /// Wraps `t` around the domain, so the gradient tiles
+    Repeat,
+    /// Ping-pongs `t` back and forth across the domain, so every other
+    /// tile is reversed
+    Mirror,
+}
+
+/// A problem found by `Gradient::validate()`, each carrying the index
+/// of the offending stop.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum GradientIssue {
+    /// The stop's position is before the previous stop's
+    Unsorted(usize),
+    /// The stop's position is `NaN`
+    NanPosition(usize),
+    /// The stop has the same position as the previous stop
+    DuplicatePosition(usize),
+    /// A channel of the stop's colour, alpha included, is outside of `[0, 1]` or `NaN`
+    OutOfRangeColour(usize),
+    /// The stop is within `NEAR_DUPLICATE_DISTANCE` of the previous stop
+    /// but has a different colour, making an almost hard edge
+    NearDuplicate(usize),
+}
+
+/// How close two stops of different colours can be before
+/// `Gradient::validate()` reports a `GradientIssue::NearDuplicate`
+pub const NEAR_DUPLICATE_DISTANCE: f64 = 1e-6f64;
+
+/// Errors produced when building a `Gradient`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GradientError {
+    /// Fewer than 2 stops were asked for, too few to span `[0, 1]`
+    TooFewStops(usize),
+    /// The row is outside of the image, or the image has no pixels
+    InvalidRow(u32),
+}
+
+impl fmt::Display for GradientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GradientError::TooFewStops(stops) => write!(f, "{stops} stops is below 2"),
+            GradientError::InvalidRow(y) => write!(f, "row {y} is outside of the image"),
+        }
+    }
+}
+
+impl core::error::Error for GradientError {}
+
+/// Interpolates linearly between two colours, alpha included
+fn lerp(from: Colour, to: Colour, t: f64) -> Colour {
+    (from + (to - from) * t).with_alpha(from.a + (to.a - from.a) * t)
+}
+
+/// Normalises `t` within the segment and interpolates between its colours
+fn interpolate_segment<F: FnOnce(Colour, Colour, f64) -> Colour>(
+    ((t_from, from), (t_to, to)): (GradientStop, GradientStop),
+    t: f64,
+    interpolator: F,
+) -> Colour {
+    let normalised_t = (t - t_from) / (t_to - t_from);
+    interpolator(
+        from,
+        to,
+        if normalised_t.is_finite() {
+            normalised_t
+        } else {
+            1f64
+        },
+    )
+}
+
+/// The `i`th of `n` evenly spaced positions over `[t_min, t_max]`,
+/// a single position is `t_min`
+fn even_position(i: usize, n: usize, (t_min, t_max): (f64, f64)) -> f64 {
+    if n == 1 {
+        t_min
+    } else {
+        t_min + (t_max - t_min) * i as f64 / (n - 1) as f64
+    }
+}
+
+/// Reads a colour out of a lookup table baked by `Gradient::as_lut()`,
+/// linearly interpolating between the two nearest entries. `domain`
+/// is the gradient's `.domain()` that the table was baked over, any
+/// `t` outside of it is clamped.
+///
+/// # Panics
+///
+/// Panics if `lut` is empty.
+///
+/// # Example
+///
+/// ```
+/// use approx::assert_relative_eq;
+/// use tcolour::{lut_sample, Gradient, Colour};
+/// let gradient = Gradient(vec![
+///     (-1.0, Colour::grey(0.0)),
+///     (1.0, Colour::grey(1.0)),
+/// ]);
+/// let lut = gradient.as_lut(256);
+///
+/// assert_relative_eq!(lut_sample(&lut, 0.5, gradient.domain()), gradient.sample(0.5));
+/// ```
+pub fn lut_sample(lut: &[Colour], t: f64, (t_min, t_max): (f64, f64)) -> Colour {
+    assert!(!lut.is_empty(), "cannot sample an empty lookup table");
+    let x = (t - t_min) / (t_max - t_min);
+    let x = if x.is_nan() {
+        0f64
+    } else {
+        x.clamp(0f64, 1f64)
+    } * (lut.len() - 1) as f64;
+    let index = (x.floor() as usize).min(lut.len().saturating_sub(2));
+    match lut.get(index + 1) {
+        Some(&to) => lerp(lut[index], to, x - index as f64),
+        None => lut[index],
+    }
+}
+
 impl Gradient {
+    /// Creates a gradient from stops in any order, sorting them by
+    /// `t`. Stops at the same position keep their relative order and
+    /// `NaN` positions are sorted to the end.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tcolour::{Gradient, Colour};
+    /// let gradient = Gradient::new(vec![
+    ///     (1.0, Colour::grey(1.0)),
+    ///     (0.0, Colour::grey(0.0)),
+    /// ]);
+    ///
+    /// assert_eq!(gradient.sample(0.5), Colour::grey(0.5));
+    /// ```
+    pub fn new(stops: Vec<GradientStop>) -> Gradient {
+        let mut gradient = Gradient(stops);
+        gradient.sort();
+        gradient
+    }
+
+    /// Sorts the stops by `t` like `Gradient::new()`
+    pub fn sort(&mut self) {
+        // `total_cmp` alone would put negative `NaN`s first
+        self.0
+            .sort_by(|(a, _), (b, _)| a.is_nan().cmp(&b.is_nan()).then(a.total_cmp(b)));
+    }
+
+    /// Creates a gradient of `stops` fully saturated colours evenly
+    /// spaced around the hue wheel over `[0, 1]`, starting and ending
+    /// on red so that it wraps seamlessly for cyclic sampling. With `7`
+    /// stops, one every `60°`, linear sampling follows the HSV hue
+    /// wheel exactly.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::{Gradient, Colour};
+    /// let rainbow = Gradient::rainbow(7);
+    ///
+    /// assert_relative_eq!(rainbow.sample(0.0), Colour::red(1.0));
+    /// assert_relative_eq!(rainbow.sample(1.0 / 3.0), Colour::green(1.0));
+    /// assert_relative_eq!(rainbow.sample(0.25), Colour::solid(0.5, 1.0, 0.0));
+    /// assert_relative_eq!(rainbow.sample(1.0), Colour::red(1.0));
+    /// ```
+    pub fn rainbow(stops: usize) -> Gradient {
+        Gradient(
+            (0..stops)
+                .map(|i| {
+                    let t = if stops == 1 {
+                        0f64
+                    } else {
+                        i as f64 / (stops - 1) as f64
+                    };
+                    (t, Colour::from_hsl(360f64 * t, 1f64, 0.5f64))
+                })
+                .collect(),
+        )
+    }
+
+    /// Creates a gradient of `n` random solid colours, see
+    /// `Colour::random()`, at sorted uniformly random positions
+    /// between `[0, 1]`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::SeedableRng;
+    /// use tcolour::Gradient;
+    ///
+    /// let mut rng = rand::rngs::StdRng::seed_from_u64(0xbeef);
+    /// let gradient = Gradient::random(&mut rng, 8);
+    /// assert_eq!(gradient.0.len(), 8);
+    /// assert!(gradient.validate().is_empty());
+    /// ```
+    #[cfg(feature = "rand")]
+    pub fn random<R: rand::Rng + ?Sized>(rng: &mut R, n: usize) -> Gradient {
+        Gradient::new(
+            (0..n)
+                .map(|_| (rng.random_range(0f64..=1f64), Colour::random(rng)))
+                .collect(),
+        )
+    }
+
+    /// Creates a gradient over `[0, 1]` from `stops` evenly spaced
+    /// pixels across row `y` of `image`, the first and last stops being
+    /// the pixels at either end of the row. For importing gradients
+    /// painted in other tools.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use image::{Rgba, RgbaImage};
+    /// use tcolour::{Gradient, Colour};
+    ///
+    /// let image = RgbaImage::from_fn(256, 1, |x, _| Rgba([x as u8, 0, 0, 255]));
+    /// let gradient = Gradient::from_image_row(&image, 0, 3).unwrap();
+    ///
+    /// assert_eq!(gradient.0[0], (0.0, Colour::grey(0.0)));
+    /// assert_eq!(gradient.0[2], (1.0, Colour::red(1.0)));
+    /// ```
+    #[cfg(feature = "image")]
+    pub fn from_image_row(
+        image: &image::RgbaImage,
+        y: u32,
+        stops: usize,
+    ) -> Result<Gradient, GradientError> {
+        if stops < 2 {
+            return Err(GradientError::TooFewStops(stops));
+        }
+        if y >= image.height() || image.width() == 0 {
+            return Err(GradientError::InvalidRow(y));
+        }
+        let last = (image.width() - 1) as f64;
+        Ok(Gradient(
+            (0..stops)
+                .map(|i| {
+                    let t = even_position(i, stops, (0f64, 1f64));
+                    let x = (t * last).round() as u32;
+                    (t, Colour::from(*image.get_pixel(x, y)))
+                })
+                .collect(),
+        ))
+    }
+
     /// Inserts (t: f64, colour: Colour) in the region that `t` resides
     /// if `t` exists, this will replace the colour.
     pub fn insert(&mut self, t: f64, colour: Colour) {
@@ -13,8 +334,7 @@ impl Gradient {
             .0
             .iter()
             .enumerate()
-            .skip_while(|(_, (v, _))| *v < t)
-            .next()
+            .find(|(_, (v, _))| *v >= t)
             .map(|(i, (_, _))| i)
         {
             if self.0[index].0 == t {
@@ -27,6 +347,432 @@ impl Gradient {
         };
     }
 
+    /// Inserts every stop with `.insert()`, so the gradient stays sorted
+    /// and a stop at an existing `t` replaces its colour.
+    pub fn insert_many(&mut self, stops: impl IntoIterator<Item = GradientStop>) {
+        for (t, colour) in stops {
+            self.insert(t, colour);
+        }
+    }
+
+    /// Inserts a stop with `.insert()` and returns the gradient, for
+    /// building a gradient up stop by stop in any order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tcolour::{Gradient, Colour};
+    /// let gradient = Gradient::new(vec![])
+    ///     .with_stop(1.0, Colour::grey(1.0))
+    ///     .with_stop(0.0, Colour::grey(0.0));
+    ///
+    /// assert_eq!(gradient.0, vec![(0.0, Colour::grey(0.0)), (1.0, Colour::grey(1.0))]);
+    /// ```
+    pub fn with_stop(mut self, t: f64, colour: Colour) -> Self {
+        self.insert(t, colour);
+        self
+    }
+
+    /// Removes the stop exactly at `t` and returns its colour, or `None`
+    /// if there isn't one. If several stops share `t` only the first is
+    /// removed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tcolour::{Gradient, Colour};
+    /// let mut gradient = Gradient(vec![
+    ///     (0.0, Colour::grey(0.0)),
+    ///     (0.5, Colour::grey(0.8)),
+    ///     (1.0, Colour::grey(1.0)),
+    /// ]);
+    ///
+    /// assert_eq!(gradient.remove(0.5), Some(Colour::grey(0.8)));
+    /// assert_eq!(gradient.remove(0.5), None);
+    /// assert_eq!(gradient.len(), 2);
+    /// ```
+    pub fn remove(&mut self, t: f64) -> Option<Colour> {
+        let index = self.0.iter().position(|&(v, _)| v == t)?;
+        Some(self.0.remove(index).1)
+    }
+
+    /// Removes the stop closest to `t` and returns it, or `None` if the
+    /// gradient is empty. Ties go to the first stop.
+    pub fn remove_nearest(&mut self, t: f64) -> Option<GradientStop> {
+        let index = self
+            .0
+            .iter()
+            .enumerate()
+            .min_by(|(_, (a, _)), (_, (b, _))| (a - t).abs().total_cmp(&(b - t).abs()))?
+            .0;
+        Some(self.0.remove(index))
+    }
+
+    /// The number of stops
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether the gradient has no stops
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Removes every stop
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Adds stops at `t_low` and `t_high` by linearly extending the
+    /// first and last segments, the extrapolated colours are clamped
+    /// between `[0, 1]`. A bound that already lies within the gradient
+    /// is ignored and a single stop gradient is extended flatly.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::{Gradient, Colour};
+    /// let mut gradient = Gradient(vec![
+    ///     (0.25, Colour::grey(0.25)),
+    ///     (0.5, Colour::grey(0.5)),
+    /// ]);
+    /// gradient.extend(0.0, 1.0);
+    ///
+    /// assert_relative_eq!(gradient.sample(0.0), Colour::grey(0.0));
+    /// assert_relative_eq!(gradient.sample(1.0), Colour::grey(1.0));
+    /// ```
+    pub fn extend(&mut self, t_low: f64, t_high: f64) {
+        let extrapolate = |(t_from, from): GradientStop, (t_to, to): GradientStop, t: f64| {
+            let span = t_to - t_from;
+            let t = if span == 0f64 {
+                0f64
+            } else {
+                (t - t_from) / span
+            };
+            from.map_rgba_with(to, |from, to| from + (to - from) * t)
+                .clamped()
+        };
+        let len = self.0.len();
+        if len == 0 {
+            return;
+        }
+        let (first, second) = (self.0[0], self.0[1.min(len - 1)]);
+        let (penultimate, last) = (self.0[len.saturating_sub(2)], self.0[len - 1]);
+        if t_high > last.0 {
+            self.0
+                .push((t_high, extrapolate(penultimate, last, t_high)));
+        }
+        if t_low < first.0 {
+            self.0.insert(0, (t_low, extrapolate(first, second, t_low)));
+        }
+    }
+
+    /// Mirrors every stop across the gradient's domain, such that a
+    /// stop at `t` moves to `first + last - t`, keeping the stops
+    /// sorted. Modifies `self` in place.
+    pub fn reverse(&mut self) {
+        let (Some(&(t_first, _)), Some(&(t_last, _))) = (self.0.first(), self.0.last()) else {
+            return;
+        };
+        self.0.reverse();
+        self.0
+            .iter_mut()
+            .for_each(|(t, _)| *t = t_first + t_last - *t);
+    }
+
+    /// Mirrors every stop across the gradient's domain, such that a
+    /// stop at `t` moves to `first + last - t`, keeping the stops
+    /// sorted. Returns the result.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tcolour::{Gradient, Colour};
+    /// let gradient = Gradient(vec![
+    ///     (0.0, Colour::grey(0.0)),
+    ///     (0.25, Colour::grey(0.5)),
+    ///     (1.0, Colour::grey(1.0)),
+    /// ]);
+    ///
+    /// assert_eq!(gradient.reversed().0, vec![
+    ///     (0.0, Colour::grey(1.0)),
+    ///     (0.75, Colour::grey(0.5)),
+    ///     (1.0, Colour::grey(0.0)),
+    /// ]);
+    /// ```
+    pub fn reversed(&self) -> Gradient {
+        let mut reversed = self.clone();
+        reversed.reverse();
+        reversed
+    }
+
+    /// Returns the smallest and largest stop positions as `(min, max)`.
+    /// A single stop gradient returns `(t, t)` and an empty gradient
+    /// returns `(0, 0)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tcolour::{Gradient, Colour};
+    /// let gradient = Gradient(vec![
+    ///     (0.5, Colour::solid(1.0, 0.0, 0.0)),
+    ///     (0.7, Colour::solid(0.0, 1.0, 0.0)),
+    ///     (0.8, Colour::solid(0.0, 0.0, 1.0)),
+    /// ]);
+    ///
+    /// assert_eq!(gradient.domain(), (0.5, 0.8));
+    /// ```
+    pub fn domain(&self) -> (f64, f64) {
+        let Some(&(t_first, _)) = self.0.first() else {
+            return (0f64, 0f64);
+        };
+        self.0
+            .iter()
+            .fold((t_first, t_first), |(min, max), &(t, _)| {
+                (min.min(t), max.max(t))
+            })
+    }
+
+    /// Linearly rescales every stop's position such that the domain
+    /// becomes `[0, 1]`, keeping the colours intact. A gradient whose
+    /// domain is a single point is returned unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tcolour::{Gradient, Colour};
+    /// let gradient = Gradient(vec![
+    ///     (-1.0, Colour::grey(0.0)),
+    ///     (0.5, Colour::grey(0.5)),
+    ///     (1.0, Colour::grey(1.0)),
+    /// ]);
+    ///
+    /// assert_eq!(gradient.normalised_domain().0, vec![
+    ///     (0.0, Colour::grey(0.0)),
+    ///     (0.75, Colour::grey(0.5)),
+    ///     (1.0, Colour::grey(1.0)),
+    /// ]);
+    /// ```
+    pub fn normalised_domain(&self) -> Gradient {
+        let (t_min, t_max) = self.domain();
+        let span = t_max - t_min;
+        if span == 0f64 {
+            return self.clone();
+        }
+        Gradient(
+            self.0
+                .iter()
+                .map(|&(t, colour)| ((t - t_min) / span, colour))
+                .collect(),
+        )
+    }
+
+    /// Linearly remaps the gradient's `.domain()` onto `[t_min, t_max]`,
+    /// keeping the colours intact. A gradient whose domain is a single
+    /// point has its stops moved to `t_min`. `t_min` is expected to be
+    /// below `t_max`, otherwise the stops are left in descending order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tcolour::{Gradient, Colour};
+    /// let gradient = Gradient(vec![
+    ///     (0.0, Colour::grey(0.0)),
+    ///     (0.75, Colour::grey(0.5)),
+    ///     (1.0, Colour::grey(1.0)),
+    /// ]);
+    ///
+    /// // Ready for noise in [-1, 1]
+    /// assert_eq!(gradient.with_domain(-1.0, 1.0).0, vec![
+    ///     (-1.0, Colour::grey(0.0)),
+    ///     (0.5, Colour::grey(0.5)),
+    ///     (1.0, Colour::grey(1.0)),
+    /// ]);
+    /// ```
+    pub fn with_domain(self, t_min: f64, t_max: f64) -> Gradient {
+        let (from_min, from_max) = self.domain();
+        let span = from_max - from_min;
+        Gradient(
+            self.0
+                .into_iter()
+                .map(|(t, colour)| {
+                    let normalised_t = if span == 0f64 {
+                        0f64
+                    } else {
+                        (t - from_min) / span
+                    };
+                    (t_min + (t_max - t_min) * normalised_t, colour)
+                })
+                .collect(),
+        )
+    }
+
+    /// Stitches `other` onto this gradient, shifting its stops so that
+    /// its domain starts at `at`, for assembling a long gradient from
+    /// reusable pieces. The stops are sorted, so starting `other` at the
+    /// end of this domain makes a hard edge at the join where `other`'s
+    /// first colour takes over.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::{Gradient, Colour};
+    /// let shallows = Gradient(vec![
+    ///     (0.0, Colour::blue(0.5)),
+    ///     (1.0, Colour::blue(1.0)),
+    /// ]);
+    /// let land = Gradient(vec![
+    ///     (0.0, Colour::green(0.5)),
+    ///     (0.5, Colour::green(1.0)),
+    /// ]);
+    ///
+    /// let terrain = shallows.concat(&land, 1.0);
+    /// assert_eq!(terrain.domain(), (0.0, 1.5));
+    /// assert_relative_eq!(terrain.sample(1.25), Colour::green(0.75));
+    /// ```
+    pub fn concat(&self, other: &Gradient, at: f64) -> Gradient {
+        let (t_other, _) = other.domain();
+        self.0
+            .iter()
+            .copied()
+            .chain(
+                other
+                    .0
+                    .iter()
+                    .map(|&(t, colour)| (t - t_other + at, colour)),
+            )
+            .collect()
+    }
+
+    /// Applies `f` to every stop's colour and returns the result,
+    /// keeping the positions intact. Use `.map_colours()` to do this
+    /// in place.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::{Gradient, Colour};
+    /// let gradient = Gradient(vec![
+    ///     (0.0, Colour::grey(0.2)),
+    ///     (1.0, Colour::grey(0.8)),
+    /// ]);
+    ///
+    /// let night = gradient.map(|colour| colour * 0.5);
+    /// assert_relative_eq!(night.sample(1.0), Colour::grey(0.4));
+    /// ```
+    pub fn map<F: Fn(Colour) -> Colour>(&self, f: F) -> Gradient {
+        Gradient(self.0.iter().map(|&(t, colour)| (t, f(colour))).collect())
+    }
+
+    /// Applies `f` to every stop's colour in place, see `.map()`.
+    pub fn map_colours<F: Fn(Colour) -> Colour>(&mut self, f: F) {
+        for (_, colour) in self.0.iter_mut() {
+            *colour = f(*colour);
+        }
+    }
+
+    /// Inverts every stop's colour with `Colour::inverted()`, keeping the
+    /// positions and alpha. Modifies `self` in place.
+    pub fn invert(&mut self) {
+        self.map_colours(Colour::inverted);
+    }
+
+    /// Inverts every stop's colour with `Colour::inverted()`, keeping
+    /// the positions and alpha. Returns the result.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::{Gradient, Colour};
+    /// let light = Gradient(vec![
+    ///     (0.0, Colour::grey(0.9)),
+    ///     (1.0, Colour::grey(0.6)),
+    /// ]);
+    ///
+    /// let dark = light.inverted();
+    /// assert_relative_eq!(dark.sample(0.0), Colour::grey(0.1));
+    /// assert_relative_eq!(dark.sample(1.0), Colour::grey(0.4));
+    /// ```
+    pub fn inverted(&self) -> Gradient {
+        self.map(Colour::inverted)
+    }
+
+    /// Applies `f` to every stop's position and returns the result,
+    /// keeping the colours intact. Shifting, scaling and normalising
+    /// are all special cases, but `f` can be any remap such as a log
+    /// or gamma curve.
+    ///
+    /// The stops are re-sorted afterwards so the result is always
+    /// sorted. If `f` isn't monotonic this can reorder the colours and
+    /// stops that `f` maps to the same position collapse onto one
+    /// another (keeping their original relative order).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tcolour::{Gradient, Colour};
+    /// let gradient = Gradient(vec![
+    ///     (0.0, Colour::grey(0.0)),
+    ///     (0.5, Colour::grey(0.5)),
+    ///     (1.0, Colour::grey(1.0)),
+    /// ]);
+    ///
+    /// assert_eq!(gradient.map_positions(|t| t * 2.0 - 1.0).0, vec![
+    ///     (-1.0, Colour::grey(0.0)),
+    ///     (0.0, Colour::grey(0.5)),
+    ///     (1.0, Colour::grey(1.0)),
+    /// ]);
+    /// ```
+    pub fn map_positions(&self, f: impl Fn(f64) -> f64) -> Gradient {
+        Gradient::new(self.0.iter().map(|&(t, colour)| (f(t), colour)).collect())
+    }
+
+    /// Checks the gradient for problems, such as those in an imported
+    /// gradient, returning every issue found in order of the stops.
+    /// An empty `Vec` means the gradient is healthy.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tcolour::{Gradient, GradientIssue, Colour};
+    /// let gradient = Gradient(vec![
+    ///     (0.0, Colour::grey(0.0)),
+    ///     (0.5, Colour::grey(2.0)),
+    ///     (0.25, Colour::grey(1.0)),
+    /// ]);
+    ///
+    /// assert_eq!(
+    ///     gradient.validate(),
+    ///     vec![GradientIssue::OutOfRangeColour(1), GradientIssue::Unsorted(2)]
+    /// );
+    /// ```
+    pub fn validate(&self) -> Vec<GradientIssue> {
+        let mut issues = Vec::new();
+        for (i, &(t, colour)) in self.0.iter().enumerate() {
+            if t.is_nan() {
+                issues.push(GradientIssue::NanPosition(i));
+            }
+            if !colour.all_rgba(|v| (0f64..=1f64).contains(&v)) {
+                issues.push(GradientIssue::OutOfRangeColour(i));
+            }
+            let Some(&(t_previous, previous)) = i.checked_sub(1).map(|j| &self.0[j]) else {
+                continue;
+            };
+            if t < t_previous {
+                issues.push(GradientIssue::Unsorted(i));
+            } else if t == t_previous {
+                issues.push(GradientIssue::DuplicatePosition(i));
+            } else if t - t_previous < NEAR_DUPLICATE_DISTANCE && colour != previous {
+                issues.push(GradientIssue::NearDuplicate(i));
+            }
+        }
+        issues
+    }
+
     /// Returns the two gradient stops that `t` resides between.
     ///
     /// Much like a quadratic solution, if `t` resides before the first
@@ -89,17 +835,585 @@ impl Gradient {
     /// assert_eq!(gradient.sample(0.9), Colour::solid(0.0, 0.0, 1.0));
     /// ```
     pub fn sample(&self, t: f64) -> Colour {
-        self.interpolate(t, |from, to, t| {
-            (from + (to - from) * t).with_alpha(from.a + (to.a - from.a) * t)
-        })
+        self.interpolate(t, lerp)
     }
 
-    /// Gets a colour from the gradient by finding
-    /// the region that contains `t` and then interpolating
-    /// using the function that is given.
+    /// Gets a colour from the gradient like `.sample()` but with
+    /// `policy` deciding what happens outside of the gradient's stops.
+    /// Inside of them this is identical to `.sample()`.
     ///
-    /// `t` is normalised between `t_from` and `t_to` by
-    /// `t = (t - t_from)/(t_to - t_from)` such that
+    /// `Gradient` is a public tuple of its stops so it has nowhere to
+    /// keep a policy of its own, the policy is given per call instead.
+    /// Use `.with_edge_policy()` to keep one alongside the gradient.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::{EdgePolicy, Gradient, Colour};
+    /// let gradient = Gradient(vec![
+    ///     (0.25, Colour::grey(0.25)),
+    ///     (0.5, Colour::grey(0.5)),
+    /// ]);
+    ///
+    /// assert_relative_eq!(gradient.sample_with_policy(0.0, EdgePolicy::Clamp), Colour::grey(0.25));
+    /// assert_relative_eq!(gradient.sample_with_policy(0.0, EdgePolicy::Extrapolate), Colour::grey(0.0));
+    /// assert_relative_eq!(gradient.sample_with_policy(0.0, EdgePolicy::Transparent), Colour::transparent());
+    /// ```
+    pub fn sample_with_policy(&self, t: f64, policy: EdgePolicy) -> Colour {
+        let len = self.0.len();
+        let outside = match (self.0.first(), self.0.last()) {
+            (Some(&(t_first, _)), _) if t < t_first => Some((self.0[0], self.0[1.min(len - 1)])),
+            (_, Some(&(t_last, _))) if t > t_last => {
+                Some((self.0[len.saturating_sub(2)], self.0[len - 1]))
+            }
+            _ => None,
+        };
+        match (outside, policy) {
+            (None, _) | (_, EdgePolicy::Clamp) => self.sample(t),
+            (Some(_), EdgePolicy::Transparent) => Colour::transparent(),
+            (Some(((t_from, from), (t_to, to))), EdgePolicy::Extrapolate) => {
+                let span = t_to - t_from;
+                let t = if span == 0f64 {
+                    0f64
+                } else {
+                    (t - t_from) / span
+                };
+                lerp(from, to, t).clamped()
+            }
+        }
+    }
+
+    /// Samples the gradient at `t` after wrapping it into the domain
+    /// with `mode`, for tiling gradients across a texture.
+    /// `WrapMode::Repeat` treats the domain as half-open so the end of
+    /// the domain wraps to the start, and a gradient without a domain
+    /// to wrap in is only clamped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::{Gradient, Colour, WrapMode};
+    /// let gradient = Gradient(vec![
+    ///     (0.0, Colour::grey(0.0)),
+    ///     (1.0, Colour::grey(1.0)),
+    /// ]);
+    ///
+    /// assert_relative_eq!(gradient.sample_wrapped(1.25, WrapMode::Clamp), Colour::grey(1.0));
+    /// assert_relative_eq!(gradient.sample_wrapped(1.25, WrapMode::Repeat), Colour::grey(0.25));
+    /// assert_relative_eq!(gradient.sample_wrapped(1.25, WrapMode::Mirror), Colour::grey(0.75));
+    /// ```
+    pub fn sample_wrapped(&self, t: f64, mode: WrapMode) -> Colour {
+        let (t_min, t_max) = self.domain();
+        let len = t_max - t_min;
+        if len <= 0f64 {
+            return self.sample(t);
+        }
+        let t = match mode {
+            WrapMode::Clamp => t,
+            WrapMode::Repeat => t_min + (t - t_min).rem_euclid(len),
+            WrapMode::Mirror => {
+                let offset = (t - t_min).rem_euclid(2f64 * len);
+                t_min
+                    + if offset > len {
+                        2f64 * len - offset
+                    } else {
+                        offset
+                    }
+            }
+        };
+        self.sample(t)
+    }
+
+    /// Samples the gradient at `t` and composes the sample over
+    /// `background` with `Colour::compose()`, for previewing a
+    /// translucent gradient over a checkerboard or a page.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::{Gradient, Colour};
+    /// let fade = Gradient(vec![
+    ///     (0.0, Colour::grey(0.0)),
+    ///     (1.0, Colour::transparent()),
+    /// ]);
+    ///
+    /// assert_relative_eq!(fade.sample_over(1.0, Colour::grey(1.0)), Colour::grey(1.0));
+    /// assert_relative_eq!(fade.sample_over(0.5, Colour::grey(1.0)), Colour::grey(0.5));
+    /// ```
+    pub fn sample_over(&self, t: f64, background: Colour) -> Colour {
+        background.compose(self.sample(t))
+    }
+
+    /// Samples the gradient at every `t` in `ts`, identical to calling
+    /// `.sample()` on each but the stops are only walked once rather
+    /// than scanned for every `t`. The `ts` can be in any order, the
+    /// colours are returned in the same order as `ts`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tcolour::{Gradient, Colour};
+    /// let gradient = Gradient(vec![
+    ///     (0.0, Colour::grey(0.0)),
+    ///     (1.0, Colour::grey(1.0)),
+    /// ]);
+    ///
+    /// assert_eq!(
+    ///     gradient.sample_many(&[0.5, 0.0, 1.0]),
+    ///     vec![Colour::grey(0.5), Colour::grey(0.0), Colour::grey(1.0)]
+    /// );
+    /// ```
+    pub fn sample_many(&self, ts: &[f64]) -> Vec<Colour> {
+        let mut order: Vec<usize> = (0..ts.len()).filter(|&i| !ts[i].is_nan()).collect();
+        order.sort_by(|&a, &b| ts[a].total_cmp(&ts[b]));
+
+        let mut colours = vec![Colour::transparent(); ts.len()];
+        (0..ts.len())
+            .filter(|&i| ts[i].is_nan())
+            .for_each(|i| colours[i] = self.sample(ts[i]));
+
+        // The first stop beyond `t` only ever moves forward as `t` increases,
+        // stops with a NaN position are never beyond `t` just like `.subgradient()`
+        let mut upper = 0;
+        for i in order {
+            let t = ts[i];
+            while upper < self.0.len() && self.0[upper].0.partial_cmp(&t) != Some(Ordering::Greater)
+            {
+                upper += 1;
+            }
+            let segment = match upper {
+                0 => (self.0[0], self.0[0]),
+                upper if upper == self.0.len() => (self.0[upper - 1], self.0[upper - 1]),
+                upper => (self.0[upper - 1], self.0[upper]),
+            };
+            colours[i] = interpolate_segment(segment, t, lerp);
+        }
+        colours
+    }
+
+    /// Bakes the gradient into `size` evenly spaced samples over its
+    /// `.domain()`, the first and last entries being the colours at
+    /// either end. Use `lut_sample()` to read colours back out of it
+    /// without touching the stops at all.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tcolour::{Gradient, Colour};
+    /// let gradient = Gradient(vec![
+    ///     (-1.0, Colour::grey(0.0)),
+    ///     (1.0, Colour::grey(1.0)),
+    /// ]);
+    ///
+    /// assert_eq!(
+    ///     gradient.as_lut(3),
+    ///     vec![Colour::grey(0.0), Colour::grey(0.5), Colour::grey(1.0)]
+    /// );
+    /// ```
+    pub fn as_lut(&self, size: usize) -> Vec<Colour> {
+        let domain = self.domain();
+        let ts: Vec<f64> = (0..size).map(|i| even_position(i, size, domain)).collect();
+        self.sample_many(&ts)
+    }
+
+    /// Bakes the gradient into `size` RGBA8 texels like `.as_lut()`,
+    /// but interpolates the stops in linear light before re-encoding
+    /// to sRGB. Ready to upload as an sRGB texture, without the banding
+    /// and dark midpoints of interpolating the encoded channels.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tcolour::{Gradient, Colour};
+    /// let gradient = Gradient(vec![
+    ///     (0.0, Colour::grey(0.0)),
+    ///     (1.0, Colour::grey(1.0)),
+    /// ]);
+    ///
+    /// assert_eq!(
+    ///     gradient.as_lut_linear(3),
+    ///     vec![[0, 0, 0, 255], [188, 188, 188, 255], [255, 255, 255, 255]]
+    /// );
+    /// ```
+    pub fn as_lut_linear(&self, size: usize) -> Vec<[u8; 4]> {
+        self.map(|colour| colour.to_linear())
+            .as_lut(size)
+            .into_iter()
+            .map(|colour| {
+                // Rounded, the round trip through linear light can land a hair below a level
+                let (r, g, b, a) = colour.to_srgb().to_u8_rgba_saturating();
+                [r, g, b, a]
+            })
+            .collect()
+    }
+
+    /// Renders the gradient as a horizontal strip, `width` evenly spaced
+    /// samples over its `.domain()` from left to right like `.as_lut()`,
+    /// for previewing it. Every row is the same.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the image isn't empty and the gradient has no stops.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tcolour::{Gradient, Colour};
+    /// let gradient = Gradient(vec![
+    ///     (0.0, Colour::grey(0.0)),
+    ///     (1.0, Colour::grey(1.0)),
+    /// ]);
+    ///
+    /// let image = gradient.to_image(256, 16);
+    /// assert_eq!(image.dimensions(), (256, 16));
+    /// assert_eq!(image.get_pixel(255, 8).0, [255, 255, 255, 255]);
+    /// ```
+    #[cfg(feature = "image")]
+    pub fn to_image(&self, width: u32, height: u32) -> image::RgbaImage {
+        let lut = if height == 0 {
+            Vec::new()
+        } else {
+            self.as_lut(width as usize)
+        };
+        image::RgbaImage::from_fn(width, height, |x, _| lut[x as usize].into())
+    }
+
+    /// Renders the gradient as a vertical strip from top to bottom, see
+    /// `.to_image()`.
+    #[cfg(feature = "image")]
+    pub fn to_image_vertical(&self, width: u32, height: u32) -> image::RgbaImage {
+        let lut = if width == 0 {
+            Vec::new()
+        } else {
+            self.as_lut(height as usize)
+        };
+        image::RgbaImage::from_fn(width, height, |_, y| lut[y as usize].into())
+    }
+
+    /// Samples the gradient at `N` evenly spaced points over its
+    /// `.domain()` like `.as_lut()`, but into an array on the stack
+    /// for small fixed size tables such as a 16 colour palette.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is not zero and the gradient has no stops.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tcolour::{Gradient, Colour};
+    /// let gradient = Gradient(vec![
+    ///     (-1.0, Colour::grey(0.0)),
+    ///     (1.0, Colour::grey(1.0)),
+    /// ]);
+    ///
+    /// let palette: [Colour; 3] = gradient.sample_array();
+    /// assert_eq!(palette, [Colour::grey(0.0), Colour::grey(0.5), Colour::grey(1.0)]);
+    /// ```
+    pub fn sample_array<const N: usize>(&self) -> [Colour; N] {
+        let domain = self.domain();
+        core::array::from_fn(|i| self.sample(even_position(i, N, domain)))
+    }
+
+    /// Iterates over `n` evenly spaced `(t, colour)` samples over the
+    /// gradient's `.domain()`, the first and last being either end.
+    /// Nothing is collected, so this is cheap to feed into a plot. A
+    /// single sample is the start of the domain and zero samples yield
+    /// nothing. Use `.sample_colours()` for only the colours.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tcolour::{Gradient, Colour};
+    /// let gradient = Gradient(vec![
+    ///     (-1.0, Colour::grey(0.0)),
+    ///     (1.0, Colour::grey(1.0)),
+    /// ]);
+    ///
+    /// assert_eq!(
+    ///     gradient.samples(3).collect::<Vec<_>>(),
+    ///     vec![(-1.0, Colour::grey(0.0)), (0.0, Colour::grey(0.5)), (1.0, Colour::grey(1.0))]
+    /// );
+    /// ```
+    pub fn samples(&self, n: usize) -> impl Iterator<Item = (f64, Colour)> + '_ {
+        let domain = self.domain();
+        (0..n).map(move |i| {
+            let t = even_position(i, n, domain);
+            (t, self.sample(t))
+        })
+    }
+
+    /// Iterates over the colours of `n` evenly spaced samples over the
+    /// gradient's `.domain()`, like `.samples()` without the positions.
+    /// `n = 2` gives either end, a single sample is the start of the
+    /// domain and zero samples yield nothing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tcolour::{Gradient, Colour};
+    /// let gradient = Gradient(vec![
+    ///     (0.0, Colour::grey(0.0)),
+    ///     (1.0, Colour::grey(1.0)),
+    /// ]);
+    ///
+    /// assert_eq!(
+    ///     gradient.sample_colours(3).collect::<Vec<_>>(),
+    ///     vec![Colour::grey(0.0), Colour::grey(0.5), Colour::grey(1.0)]
+    /// );
+    /// ```
+    pub fn sample_colours(&self, n: usize) -> impl Iterator<Item = Colour> + '_ {
+        self.samples(n).map(|(_, colour)| colour)
+    }
+
+    /// Samples the gradient `samples` times evenly across its domain and
+    /// snaps each sample to the nearest colour in `palette` by CIEDE2000,
+    /// ignoring alpha. The result is a stepped gradient made only of
+    /// palette colours, for palette limited hardware.
+    ///
+    /// Each sample's colour holds until halfway to the next sample where
+    /// it steps with a pair of stops at the same position, runs of the
+    /// same palette colour are merged. An empty palette or zero samples
+    /// gives an empty gradient.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tcolour::{Gradient, Colour};
+    /// let gradient = Gradient(vec![
+    ///     (0.0, Colour::grey(0.0)),
+    ///     (1.0, Colour::grey(1.0)),
+    /// ]);
+    /// let stepped = gradient.quantize_to_palette(&[Colour::grey(0.0), Colour::grey(1.0)], 16);
+    ///
+    /// assert_eq!(stepped.sample(0.1), Colour::grey(0.0));
+    /// assert_eq!(stepped.sample(0.9), Colour::grey(1.0));
+    /// ```
+    pub fn quantize_to_palette(&self, palette: &[Colour], samples: usize) -> Gradient {
+        if palette.is_empty() {
+            return Gradient(Vec::new());
+        }
+        let (t_min, t_max) = self.domain();
+        let t = |i: usize| {
+            if samples == 1 {
+                t_min
+            } else {
+                t_min + (t_max - t_min) * i as f64 / (samples - 1) as f64
+            }
+        };
+        let nearest = |colour: Colour| {
+            palette
+                .iter()
+                .copied()
+                .min_by(|a, b| colour.delta_e(a).total_cmp(&colour.delta_e(b)))
+                .unwrap()
+        };
+        let snapped: Vec<Colour> = self.as_lut(samples).into_iter().map(nearest).collect();
+
+        let mut stops = Vec::new();
+        for (i, &colour) in snapped.iter().enumerate() {
+            match stops.last() {
+                None => stops.push((t(i), colour)),
+                Some(&(_, previous)) if previous != colour => {
+                    let step = (t(i - 1) + t(i)) / 2f64;
+                    stops.push((step, previous));
+                    stops.push((step, colour));
+                }
+                Some(_) => {}
+            }
+        }
+        if samples > 1 {
+            stops.push((t(samples - 1), snapped[samples - 1]));
+        }
+        Gradient(stops)
+    }
+
+    /// Gets a colour from the gradient like `.sample()` but
+    /// with `easing` applied to the normalised `t` within
+    /// the segment before interpolating. `Easing::Linear`
+    /// is identical to `.sample()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::{Easing, Gradient, Colour};
+    /// let gradient = Gradient(vec![
+    ///     (0.0, Colour::grey(0.0)),
+    ///     (1.0, Colour::grey(1.0)),
+    /// ]);
+    ///
+    /// assert_relative_eq!(gradient.sample_eased(0.25, Easing::EaseIn), Colour::grey(0.0625));
+    /// assert_relative_eq!(gradient.sample_eased(0.5, Easing::SmoothStep), Colour::grey(0.5));
+    /// ```
+    pub fn sample_eased(&self, t: f64, easing: Easing) -> Colour {
+        self.interpolate(t, |from, to, t| lerp(from, to, easing.apply(t)))
+    }
+
+    /// Gets a colour from the gradient like `.sample()` but
+    /// interpolates the stops in OKLab rather than RGB, so
+    /// blends between saturated colours don't pass through
+    /// grey. The alpha value is interpolated linearly.
+    ///
+    /// The result is not clamped, mixes can land slightly
+    /// outside of the sRGB gamut so use `.clamped()` if necessary.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::{Gradient, Colour};
+    /// let gradient = Gradient(vec![
+    ///     (0.0, Colour::red(1.0)),
+    ///     (1.0, Colour::green(1.0)),
+    /// ]);
+    ///
+    /// assert_relative_eq!(gradient.sample_oklab(0.0), Colour::red(1.0), epsilon = 1e-6);
+    /// assert!(gradient.sample_oklab(0.5).luminance() > gradient.sample(0.5).luminance());
+    /// ```
+    pub fn sample_oklab(&self, t: f64) -> Colour {
+        self.interpolate(t, |from, to, t| {
+            let (l_from, a_from, b_from) = from.to_oklab();
+            let (l_to, a_to, b_to) = to.to_oklab();
+            Colour::from_oklab(
+                l_from + (l_to - l_from) * t,
+                a_from + (a_to - a_from) * t,
+                b_from + (b_to - b_from) * t,
+            )
+            .with_alpha(from.a + (to.a - from.a) * t)
+        })
+    }
+
+    /// Gets a colour from the gradient like `.sample()` but
+    /// interpolates the stops in linear light, decoding them with
+    /// `Colour::to_linear()` and encoding the result back to sRGB, so
+    /// the midpoint of black and white isn't too dark. The alpha value
+    /// is interpolated linearly.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::{Gradient, Colour};
+    /// let gradient = Gradient(vec![
+    ///     (0.0, Colour::grey(0.0)),
+    ///     (1.0, Colour::grey(1.0)),
+    /// ]);
+    ///
+    /// assert_relative_eq!(gradient.sample_linear(0.5), Colour::grey(0.5).to_srgb());
+    /// ```
+    pub fn sample_linear(&self, t: f64) -> Colour {
+        self.interpolate(t, |from, to, t| {
+            lerp(from.to_linear(), to.to_linear(), t).to_srgb()
+        })
+    }
+
+    /// Gets a colour from the gradient like `.sample()` but
+    /// interpolates the stops with premultiplied alpha and returns the
+    /// straight (un-premultiplied) result. A stop's colour then only
+    /// counts as much as it is opaque, so fading to `Colour::transparent()`
+    /// fades out the colour rather than darkening it towards black.
+    /// Where the interpolated alpha is `0` the colours are interpolated
+    /// as they are.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::{Gradient, Colour};
+    /// let gradient = Gradient(vec![
+    ///     (0.0, Colour::red(1.0)),
+    ///     (1.0, Colour::transparent()),
+    /// ]);
+    ///
+    /// assert_relative_eq!(gradient.sample(0.5), Colour::new(0.5, 0.0, 0.0, 0.5));
+    /// assert_relative_eq!(gradient.sample_straight(0.5), Colour::new(1.0, 0.0, 0.0, 0.5));
+    /// ```
+    pub fn sample_straight(&self, t: f64) -> Colour {
+        self.interpolate(t, |from, to, t| {
+            let alpha = from.a + (to.a - from.a) * t;
+            if alpha == 0f64 {
+                return lerp(from, to, t);
+            }
+            (lerp(from * from.a, to * to.a, t) / alpha).with_alpha(alpha)
+        })
+    }
+
+    /// Gets a colour from the gradient like `.sample()` but with a
+    /// [Catmull-Rom spline](https://en.wikipedia.org/wiki/Cubic_Hermite_spline#Catmull%E2%80%93Rom_spline)
+    /// through the stops, interpolating each channel (alpha included)
+    /// with tangents from the stops either side. The colour changes
+    /// smoothly across stops instead of kinking at them.
+    ///
+    /// The tangents are divided by the distance between the stops so
+    /// unevenly spaced stops work, and stops that lie on a straight line
+    /// sample exactly as `.sample()`. The end stops use the one stop
+    /// beside them and outside of the stops the colour is clamped.
+    /// Gradients with fewer than 4 stops fall back to `.sample()`.
+    ///
+    /// The result is not clamped, the spline can overshoot around
+    /// sharp changes so use `.clamped()` if necessary.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::{Gradient, Colour};
+    /// let gradient = Gradient(vec![
+    ///     (0.0, Colour::grey(0.0)),
+    ///     (0.25, Colour::grey(0.5)),
+    ///     (0.5, Colour::grey(0.5)),
+    ///     (1.0, Colour::grey(1.0)),
+    /// ]);
+    ///
+    /// assert_relative_eq!(gradient.sample_catmull_rom(0.25), Colour::grey(0.5));
+    /// // Curves through the stop at 0.25 rather than meeting it at an angle
+    /// assert!(gradient.sample_catmull_rom(0.2).r > gradient.sample(0.2).r);
+    /// ```
+    pub fn sample_catmull_rom(&self, t: f64) -> Colour {
+        let len = self.0.len();
+        let to = self.0.partition_point(|&(t_stop, _)| t_stop <= t);
+        if len < 4 || to == 0 || to == len {
+            return self.sample(t);
+        }
+        let slope = |i: usize| -> [f64; 4] {
+            let (t_prev, prev) = self.0[i.saturating_sub(1)];
+            let (t_next, next) = self.0[(i + 1).min(len - 1)];
+            let (prev, next): ([f64; 4], [f64; 4]) = (prev.into(), next.into());
+            let span = t_next - t_prev;
+            core::array::from_fn(|c| {
+                if span > 0f64 {
+                    (next[c] - prev[c]) / span
+                } else {
+                    0f64
+                }
+            })
+        };
+
+        let ((t_from, from), (t_to, to_colour)) = (self.0[to - 1], self.0[to]);
+        let (from, to_colour): ([f64; 4], [f64; 4]) = (from.into(), to_colour.into());
+        let (m_from, m_to) = (slope(to - 1), slope(to));
+        let width = t_to - t_from;
+        let s = (t - t_from) / width;
+        let (s2, s3) = (s * s, s * s * s);
+        // The cubic Hermite basis, the tangents scaled to the segment
+        Colour::from(core::array::from_fn::<f64, 4, _>(|c| {
+            (2f64 * s3 - 3f64 * s2 + 1f64) * from[c]
+                + (s3 - 2f64 * s2 + s) * width * m_from[c]
+                + (3f64 * s2 - 2f64 * s3) * to_colour[c]
+                + (s3 - s2) * width * m_to[c]
+        }))
+    }
+
+    /// Gets a colour from the gradient by finding
+    /// the region that contains `t` and then interpolating
+    /// using the function that is given.
+    ///
+    /// `t` is normalised between `t_from` and `t_to` by
+    /// `t = (t - t_from)/(t_to - t_from)` such that
     /// with `t = 0.7`, `t_from = 0.6` and `t_to = 0.8`
     /// we actually interpolate between `[0, 1]` with `t = 0.5`
     pub fn interpolate<F: FnOnce(Colour, Colour, f64) -> Colour>(
@@ -107,115 +1421,1728 @@ impl Gradient {
         t: f64,
         interpolator: F,
     ) -> Colour {
-        let ((t_from, from), (t_to, to)) = self.subgradient(t);
-        let normalised_t = (t - t_from) / (t_to - t_from);
-        interpolator(
-            from,
-            to,
-            normalised_t.is_normal().not().then_some(1f64).unwrap_or(normalised_t)
-        )
+        interpolate_segment(self.subgradient(t), t, interpolator)
+    }
+
+    /// Samples both gradients at `samples` evenly spaced positions and
+    /// returns the largest `Colour::distance_rgba()` between them, for
+    /// asserting a gradient stays close to a reference. Each gradient is
+    /// sampled over its own `.domain()` so gradients with different
+    /// domains are compared position for position once normalised.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `samples` is not zero and either gradient has no stops.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::{Gradient, Colour};
+    /// let reference = Gradient(vec![
+    ///     (0.0, Colour::grey(0.0)),
+    ///     (1.0, Colour::grey(1.0)),
+    /// ]);
+    /// let refactored = Gradient(vec![
+    ///     (0.0, Colour::grey(0.0)),
+    ///     (0.5, Colour::grey(0.5)),
+    ///     (1.0, Colour::grey(1.0)),
+    /// ]);
+    ///
+    /// assert_relative_eq!(reference.max_difference(&refactored, 64), 0.0, epsilon = 1e-12);
+    /// ```
+    pub fn max_difference(&self, other: &Gradient, samples: usize) -> f64 {
+        self.samples(samples)
+            .zip(other.samples(samples))
+            .map(|((_, a), (_, b))| a.distance_rgba(&b))
+            .fold(0f64, f64::max)
+    }
+
+    /// Samples the gradient `samples` times evenly across its stops
+    /// and counts the relative luminance of each sample into `bins`
+    /// equally sized bins over `[0, 1]`. A colormap that spends its
+    /// perceptual range evenly has a roughly flat histogram.
+    pub fn luminance_histogram(&self, samples: usize, bins: usize) -> Vec<usize> {
+        let mut histogram = vec![0; bins];
+        let (Some(&(t_first, _)), Some(&(t_last, _))) = (self.0.first(), self.0.last()) else {
+            return histogram;
+        };
+        if bins == 0 {
+            return histogram;
+        }
+        for i in 0..samples {
+            let t = if samples == 1 {
+                t_first
+            } else {
+                t_first + (t_last - t_first) * i as f64 / (samples - 1) as f64
+            };
+            let luminance = self.sample(t).luminance().clamp(0f64, 1f64);
+            histogram[((luminance * bins as f64) as usize).min(bins - 1)] += 1;
+        }
+        histogram
+    }
+
+    /// Select the lower bound colour, use `.select_upper()` for the upper
+    /// bound colour
+    pub fn select(&self, t: f64) -> Colour {
+        self.subgradient(t).0.1
+    }
+
+    /// Select the upper bound colour, use `.select()` for the lower bound
+    /// colour
+    pub fn select_upper(&self, t: f64) -> Colour {
+        self.subgradient(t).1.1
+    }
+
+    /// Iterates over the stops in order
+    pub fn iter(&self) -> core::slice::Iter<'_, GradientStop> {
+        self.0.iter()
+    }
+
+    /// Iterates over the stops in order, allowing them to be modified.
+    /// Moving a stop's position can leave the gradient unsorted.
+    pub fn iter_mut(&mut self) -> core::slice::IterMut<'_, GradientStop> {
+        self.0.iter_mut()
+    }
+
+    /// The stops with a `t` between `[lo, hi]`, as a slice of the sorted
+    /// stops without allocating. It's empty if no stops are in range,
+    /// `lo > hi` or either bound is `NaN`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tcolour::{Gradient, Colour};
+    /// let gradient = Gradient(vec![
+    ///     (0.5, Colour::solid(1.0, 0.0, 0.0)),
+    ///     (0.7, Colour::solid(0.0, 1.0, 0.0)),
+    ///     (0.8, Colour::solid(0.0, 0.0, 1.0)),
+    /// ]);
+    ///
+    /// assert_eq!(gradient.stops_in_range(0.55, 0.85), &gradient.0[1..]);
+    /// assert!(gradient.stops_in_range(0.1, 0.2).is_empty());
+    /// ```
+    pub fn stops_in_range(&self, lo: f64, hi: f64) -> &[GradientStop] {
+        if lo.is_nan() || hi.is_nan() {
+            return &[];
+        }
+        let start = self.0.partition_point(|&(t, _)| t < lo);
+        let end = self.0.partition_point(|&(t, _)| t <= hi);
+        self.0.get(start..end).unwrap_or(&[])
+    }
+
+    /// Pairs the gradient with an `EdgePolicy` that its `.sample()`
+    /// always uses, see `PolicyGradient`.
+    pub fn with_edge_policy(self, policy: EdgePolicy) -> PolicyGradient {
+        PolicyGradient {
+            gradient: self,
+            policy,
+        }
+    }
+}
+
+/// A `Gradient` that keeps the `EdgePolicy` it is sampled with, for
+/// passing one around that always behaves the same at its edges.
+///
+/// # Example
+///
+/// ```
+/// use tcolour::{EdgePolicy, Gradient, Colour};
+/// let mut overlay = Gradient(vec![
+///     (0.25, Colour::grey(0.25)),
+///     (0.5, Colour::grey(0.5)),
+/// ])
+/// .with_edge_policy(EdgePolicy::Transparent);
+///
+/// assert_eq!(overlay.sample(0.0), Colour::transparent());
+/// overlay.set_edge_policy(EdgePolicy::Clamp);
+/// assert_eq!(overlay.sample(0.0), Colour::grey(0.25));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct PolicyGradient {
+    pub gradient: Gradient,
+    pub policy: EdgePolicy,
+}
+
+impl PolicyGradient {
+    /// Pairs `gradient` with `policy`
+    pub fn new(gradient: Gradient, policy: EdgePolicy) -> Self {
+        Self { gradient, policy }
+    }
+
+    /// Sets the policy used outside of the gradient's stops
+    pub fn set_edge_policy(&mut self, policy: EdgePolicy) {
+        self.policy = policy;
+    }
+
+    /// Gets a colour from the gradient like `Gradient::sample()`, using
+    /// the stored policy outside of the stops.
+    pub fn sample(&self, t: f64) -> Colour {
+        self.gradient.sample_with_policy(t, self.policy)
+    }
+}
+
+/// Collects stops in any order, sorting them like `Gradient::new()`
+impl FromIterator<GradientStop> for Gradient {
+    fn from_iter<I: IntoIterator<Item = GradientStop>>(iter: I) -> Self {
+        Gradient::new(iter.into_iter().collect())
+    }
+}
+
+impl IntoIterator for Gradient {
+    type Item = GradientStop;
+    type IntoIter = <Vec<GradientStop> as IntoIterator>::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Gradient {
+    type Item = &'a GradientStop;
+    type IntoIter = core::slice::Iter<'a, GradientStop>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut Gradient {
+    type Item = &'a mut GradientStop;
+    type IntoIter = core::slice::IterMut<'a, GradientStop>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// Gradients with a different number of stops are never equal,
+/// otherwise each stop's `t` and colour are compared pairwise with the
+/// same epsilon.
+#[cfg(feature = "approx")]
+impl AbsDiffEq for Gradient {
+    type Epsilon = f64;
+    fn default_epsilon() -> Self::Epsilon {
+        Colour::default_epsilon()
+    }
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        self.0.len() == other.0.len()
+            && self
+                .0
+                .iter()
+                .zip(&other.0)
+                .all(|((t_self, c_self), (t_other, c_other))| {
+                    t_self.abs_diff_eq(t_other, epsilon) && c_self.abs_diff_eq(c_other, epsilon)
+                })
+    }
+}
+
+#[cfg(feature = "approx")]
+impl RelativeEq for Gradient {
+    fn default_max_relative() -> Self::Epsilon {
+        Colour::default_max_relative()
+    }
+    fn relative_eq(
+        &self,
+        other: &Self,
+        epsilon: Self::Epsilon,
+        max_relative: Self::Epsilon,
+    ) -> bool {
+        self.0.len() == other.0.len()
+            && self
+                .0
+                .iter()
+                .zip(&other.0)
+                .all(|((t_self, c_self), (t_other, c_other))| {
+                    t_self.relative_eq(t_other, epsilon, max_relative)
+                        && c_self.relative_eq(c_other, epsilon, max_relative)
+                })
+    }
+}
+
+#[cfg(feature = "approx")]
+impl UlpsEq for Gradient {
+    fn default_max_ulps() -> u32 {
+        Colour::default_max_ulps()
+    }
+    fn ulps_eq(&self, other: &Self, epsilon: Self::Epsilon, max_ulps: u32) -> bool {
+        self.0.len() == other.0.len()
+            && self
+                .0
+                .iter()
+                .zip(&other.0)
+                .all(|((t_self, c_self), (t_other, c_other))| {
+                    t_self.ulps_eq(t_other, epsilon, max_ulps)
+                        && c_self.ulps_eq(c_other, epsilon, max_ulps)
+                })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::{
+        Easing, EdgePolicy, Gradient, GradientIssue, PolicyGradient, WrapMode, lut_sample,
+    };
+    use crate::Colour;
+
+    #[test]
+    pub fn subgradient_test() {
+        let gradient = Gradient(vec![
+            (0.5, Colour::solid(1.0, 0.0, 0.0)),
+            (0.7, Colour::solid(0.0, 1.0, 0.0)),
+            (0.8, Colour::solid(0.0, 0.0, 1.0)),
+        ]);
+        assert_eq!(
+            gradient.subgradient(0.1),
+            (
+                (0.5, Colour::solid(1.0, 0.0, 0.0)),
+                (0.5, Colour::solid(1.0, 0.0, 0.0))
+            )
+        );
+        assert_eq!(
+            gradient.subgradient(0.6),
+            (
+                (0.5, Colour::solid(1.0, 0.0, 0.0)),
+                (0.7, Colour::solid(0.0, 1.0, 0.0))
+            )
+        );
+        assert_eq!(
+            gradient.subgradient(0.75),
+            (
+                (0.7, Colour::solid(0.0, 1.0, 0.0)),
+                (0.8, Colour::solid(0.0, 0.0, 1.0))
+            )
+        );
+        assert_eq!(
+            gradient.subgradient(0.9),
+            (
+                (0.8, Colour::solid(0.0, 0.0, 1.0)),
+                (0.8, Colour::solid(0.0, 0.0, 1.0))
+            )
+        );
+    }
+
+    #[test]
+    pub fn sample_on_stop_test() {
+        let gradient = Gradient(vec![
+            (0.5, Colour::solid(1.0, 0.0, 0.0)),
+            (0.7, Colour::solid(0.0, 1.0, 0.0)),
+            (0.8, Colour::solid(0.0, 0.0, 1.0)),
+        ]);
+        // A normalised t of 0 isn't a normal float but is still valid
+        assert_eq!(gradient.sample(0.7), Colour::solid(0.0, 1.0, 0.0));
+        assert_eq!(gradient.sample(0.5), Colour::solid(1.0, 0.0, 0.0));
+        assert_eq!(gradient.sample(0.8), Colour::solid(0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    pub fn insertion_test() {
+        let mut gradient = Gradient(vec![
+            (0.5, Colour::solid(1.0, 0.0, 0.0)),
+            (0.7, Colour::solid(0.0, 1.0, 0.0)),
+            (0.8, Colour::solid(0.0, 0.0, 1.0)),
+        ]);
+        gradient.insert(0.3, Colour::grey(0.5));
+        assert_eq!(gradient.0.first().unwrap().1, Colour::grey(0.5));
+        gradient.insert(0.9, Colour::grey(0.2));
+        assert_eq!(gradient.0.last().unwrap().1, Colour::grey(0.2));
+        gradient.insert(0.6, Colour::red(0.8));
+        assert_eq!(gradient.0[2].1, Colour::red(0.8));
+        gradient.insert(0.85, Colour::transparent());
+        assert_eq!(gradient.0[5].1, Colour::transparent());
+        assert_eq!(gradient.0[6].1, Colour::grey(0.2));
+        gradient.insert(0.5, Colour::red(1.0).with_blue(1.0));
+        assert_eq!(
+            gradient.0,
+            vec![
+                (0.3, Colour::grey(0.5)),
+                (0.5, Colour::red(1.0).with_blue(1.0)),
+                (0.6, Colour::red(0.8)),
+                (0.7, Colour::green(1.0)),
+                (0.8, Colour::blue(1.0)),
+                (0.85, Colour::transparent()),
+                (0.9, Colour::grey(0.2))
+            ]
+        );
+    }
+
+    #[test]
+    pub fn builder_test() {
+        let gradient = Gradient::new(vec![])
+            .with_stop(0.5, Colour::red(1.0))
+            .with_stop(1.0, Colour::grey(1.0))
+            .with_stop(0.0, Colour::grey(0.0))
+            .with_stop(0.75, Colour::green(1.0))
+            .with_stop(0.5, Colour::blue(1.0));
+        assert_eq!(
+            gradient.0,
+            vec![
+                (0.0, Colour::grey(0.0)),
+                (0.5, Colour::blue(1.0)),
+                (0.75, Colour::green(1.0)),
+                (1.0, Colour::grey(1.0)),
+            ]
+        );
+        assert!(gradient.validate().is_empty());
+
+        let mut many = Gradient(vec![(0.5, Colour::red(1.0))]);
+        many.insert_many([
+            (1.0, Colour::grey(1.0)),
+            (0.75, Colour::green(1.0)),
+            (0.0, Colour::grey(0.0)),
+            (0.5, Colour::blue(1.0)),
+        ]);
+        assert_eq!(many.0, gradient.0);
+        many.insert_many(vec![]);
+        assert_eq!(many.0, gradient.0);
+    }
+
+    #[test]
+    pub fn sort_test() {
+        let sorted = vec![
+            (0.0, Colour::solid(1.0, 0.0, 0.0)),
+            (0.3, Colour::solid(0.0, 1.0, 0.0)),
+            (0.3, Colour::solid(0.0, 0.5, 0.0)),
+            (0.5, Colour::solid(0.0, 0.0, 1.0)),
+            (1.0, Colour::grey(1.0)),
+        ];
+        let shuffled = vec![sorted[3], sorted[4], sorted[1], sorted[0], sorted[2]];
+        let gradient = Gradient::new(shuffled.clone());
+        // Stable, so the stops at 0.3 keep their order
+        assert_eq!(gradient.0, sorted);
+        let expected = Gradient(sorted.clone());
+        for i in 0..=20 {
+            let t = i as f64 / 20.0;
+            assert_eq!(gradient.sample(t), expected.sample(t));
+        }
+        // Without sorting, sampling is wrong
+        assert_ne!(Gradient(shuffled.clone()).sample(0.4), expected.sample(0.4));
+
+        let mut fixed = Gradient(shuffled);
+        fixed.sort();
+        assert_eq!(fixed.0, sorted);
+        assert!(
+            fixed
+                .validate()
+                .iter()
+                .all(|issue| !matches!(issue, GradientIssue::Unsorted(_)))
+        );
+
+        let with_nan = Gradient::new(vec![
+            (f64::NAN, Colour::grey(0.0)),
+            (0.5, Colour::grey(1.0)),
+        ]);
+        assert_eq!(with_nan.0[0], (0.5, Colour::grey(1.0)));
+        assert!(with_nan.0[1].0.is_nan());
+
+        // Whatever their sign
+        let with_nans = Gradient::new(vec![
+            (f64::NAN, Colour::grey(0.0)),
+            (1.0, Colour::grey(1.0)),
+            (-f64::NAN, Colour::grey(0.0)),
+            (0.0, Colour::grey(0.5)),
+        ]);
+        assert_eq!(
+            with_nans.0[..2],
+            [(0.0, Colour::grey(0.5)), (1.0, Colour::grey(1.0))]
+        );
+        assert!(with_nans.0[2..].iter().all(|(t, _)| t.is_nan()));
+    }
+
+    #[test]
+    pub fn removal_test() {
+        let (red, green, blue) = (Colour::red(1.0), Colour::green(1.0), Colour::blue(1.0));
+        let mut gradient = Gradient(vec![(0.5, red), (0.7, green), (0.8, blue)]);
+        assert_eq!(gradient.len(), 3);
+
+        assert_eq!(gradient.remove(0.7), Some(green));
+        assert_eq!(gradient.0, vec![(0.5, red), (0.8, blue)]);
+        // The neighbours now make up the segment
+        assert_eq!(gradient.subgradient(0.7), ((0.5, red), (0.8, blue)));
+        assert_eq!(gradient.remove(0.6), None);
+
+        let mut gradient = Gradient(vec![(0.5, red), (0.7, green), (0.8, blue)]);
+        assert_eq!(gradient.remove_nearest(0.74), Some((0.7, green)));
+        assert_eq!(gradient.subgradient(0.7), ((0.5, red), (0.8, blue)));
+        assert_eq!(gradient.remove_nearest(-10.0), Some((0.5, red)));
+        // Ties go to the first stop
+        gradient.insert(0.75, green);
+        gradient.insert(0.25, red);
+        assert_eq!(gradient.remove_nearest(0.5), Some((0.25, red)));
+        assert_eq!(gradient.0, vec![(0.75, green), (0.8, blue)]);
+
+        assert!(!gradient.is_empty());
+        gradient.clear();
+        assert!(gradient.is_empty());
+        assert_eq!(gradient.len(), 0);
+        assert_eq!(gradient.remove_nearest(0.5), None);
+    }
+
+    #[test]
+    pub fn interpolation_test() {
+        let gradient = Gradient(vec![
+            (0.5, Colour::solid(1.0, 0.0, 0.0)),
+            (0.7, Colour::solid(0.0, 1.0, 0.0)),
+            (0.8, Colour::solid(0.0, 0.0, 1.0)),
+        ]);
+
+        let other_gradient = Gradient(vec![(0.4, Colour::grey(1.0)), (0.6, Colour::transparent())]);
+        assert_eq!(other_gradient.sample(0.5), Colour::grey(0.5).with_alpha(0.5));
+        assert_eq!(gradient.sample(0.6), Colour::solid(0.5, 0.5, 0.0));
+        assert_relative_eq!(other_gradient.sample(0.8), Colour::transparent());
+        assert_eq!(gradient.sample(0.1), Colour::red(1.0));
+    }
+
+    #[test]
+    pub fn extension_test() {
+        let clamped = Gradient(vec![(0.25, Colour::grey(0.25)), (0.75, Colour::grey(0.75))]);
+        let mut extended = Gradient(clamped.0.clone());
+        extended.extend(0.0, 1.0);
+
+        // Clamping holds the end colours, extrapolation continues the trend
+        assert_relative_eq!(clamped.sample(0.0), Colour::grey(0.25));
+        assert_relative_eq!(clamped.sample(1.0), Colour::grey(0.75));
+        assert_relative_eq!(extended.sample(0.0), Colour::grey(0.0));
+        assert_relative_eq!(extended.sample(1.0), Colour::grey(1.0));
+        assert_relative_eq!(extended.sample(0.5), clamped.sample(0.5));
+
+        // Extrapolated colours never leave [0, 1]
+        let mut far = Gradient(clamped.0.clone());
+        far.extend(-1.0, 2.0);
+        assert_eq!(far.0.len(), 4);
+        assert_relative_eq!(far.sample(-1.0), Colour::grey(0.0));
+        assert_relative_eq!(far.sample(2.0), Colour::grey(1.0));
+
+        // Bounds inside the gradient are ignored
+        let mut inside = Gradient(clamped.0.clone());
+        inside.extend(0.5, 0.5);
+        assert_eq!(inside.0, clamped.0);
+
+        let mut single = Gradient(vec![(0.5, Colour::red(1.0))]);
+        single.extend(0.0, 1.0);
+        assert_eq!(
+            single.0,
+            vec![
+                (0.0, Colour::red(1.0)),
+                (0.5, Colour::red(1.0)),
+                (1.0, Colour::red(1.0))
+            ]
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    pub fn serde_test() {
+        let gradient = Gradient(vec![
+            (0.0, Colour::transparent()),
+            (1.0, Colour::solid(1.0, 0.5, 0.0)),
+        ]);
+        let json = serde_json::to_string(&gradient).unwrap();
+        assert_eq!(json, "[[0.0,[0.0,0.0,0.0,0.0]],[1.0,[1.0,0.5,0.0,1.0]]]");
+        assert_eq!(
+            serde_json::from_str::<Gradient>(&json).unwrap().0,
+            gradient.0
+        );
+    }
+
+    #[test]
+    pub fn luminance_histogram_test() {
+        // Greys whose luminance rises linearly with t
+        let gradient = Gradient(
+            (0..=64)
+                .map(|i| {
+                    let linear = i as f64 / 64.0;
+                    let encoded = if linear <= 0.0031308 {
+                        linear * 12.92
+                    } else {
+                        1.055 * linear.powf(1.0 / 2.4) - 0.055
+                    };
+                    (linear, Colour::grey(encoded))
+                })
+                .collect(),
+        );
+        let histogram = gradient.luminance_histogram(1000, 10);
+        assert_eq!(histogram.iter().sum::<usize>(), 1000);
+        for count in histogram {
+            assert!((90..=110).contains(&count), "{count}");
+        }
+
+        // A plain black to white ramp is bunched up in the darker bins
+        let ramp = Gradient(vec![(0.0, Colour::grey(0.0)), (1.0, Colour::grey(1.0))]);
+        let histogram = ramp.luminance_histogram(1000, 10);
+        assert!(histogram[0] > 3 * histogram[9]);
+
+        assert_eq!(Gradient(vec![]).luminance_histogram(10, 4), vec![0; 4]);
+        assert!(gradient.luminance_histogram(10, 0).is_empty());
+    }
+
+    #[test]
+    pub fn clone_test() {
+        let gradient = Gradient(vec![
+            (0.0, Colour::solid(1.0, 0.0, 0.0)),
+            (0.3, Colour::solid(0.0, 1.0, 0.0)),
+            (1.0, Colour::solid(0.0, 0.0, 1.0)),
+        ]);
+        let mut edited = gradient.clone();
+        assert_eq!(edited, gradient);
+
+        edited.insert(0.5, Colour::grey(0.5));
+        edited.0[0].1 = Colour::grey(0.0);
+        assert_ne!(edited, gradient);
+        assert_eq!(
+            gradient,
+            Gradient(vec![
+                (0.0, Colour::solid(1.0, 0.0, 0.0)),
+                (0.3, Colour::solid(0.0, 1.0, 0.0)),
+                (1.0, Colour::solid(0.0, 0.0, 1.0)),
+            ])
+        );
+        assert!(format!("{gradient:?}").starts_with("Gradient([(0.0, Colour {"));
+    }
+
+    #[cfg(feature = "approx")]
+    #[test]
+    pub fn approx_test() {
+        use approx::{assert_abs_diff_eq, assert_relative_ne, assert_ulps_eq};
+
+        let gradient = Gradient(vec![
+            (0.0, Colour::solid(1.0, 0.0, 0.0)),
+            (0.3, Colour::solid(0.0, 1.0, 0.0).with_alpha(0.5)),
+            (1.0, Colour::solid(0.0, 0.0, 1.0)),
+        ]);
+        let mut perturbed = gradient.clone();
+        perturbed.0[1].0 += 1e-9;
+        perturbed.0[2].1.g += 1e-9;
+        assert_ne!(perturbed, gradient);
+        assert_relative_eq!(perturbed, gradient);
+        assert_abs_diff_eq!(perturbed, gradient);
+        assert_relative_ne!(perturbed, gradient, epsilon = 1e-12, max_relative = 1e-12);
+        assert_ulps_eq!(gradient.normalised_domain(), gradient);
+
+        perturbed.0[0].1.a = 0.9;
+        assert_relative_ne!(perturbed, gradient, epsilon = 0.01);
+        assert_relative_eq!(perturbed, gradient, epsilon = 0.5);
+
+        // A missing stop is never equal, whatever the epsilon
+        let mut fewer = gradient.clone();
+        fewer.0.pop();
+        assert_relative_ne!(fewer, gradient, epsilon = 10.0, max_relative = 10.0);
+        assert_relative_ne!(gradient, fewer, epsilon = 10.0, max_relative = 10.0);
+        assert_relative_eq!(Gradient(vec![]), Gradient(vec![]));
+    }
+
+    #[test]
+    pub fn reverse_test() {
+        let gradient = Gradient(vec![
+            (0.0, Colour::solid(1.0, 0.0, 0.0)),
+            (0.3, Colour::solid(0.0, 1.0, 0.0)),
+            (1.0, Colour::solid(0.0, 0.0, 1.0)),
+        ]);
+        let reversed = gradient.reversed();
+        for i in 0..=20 {
+            let t = i as f64 / 20.0;
+            assert_relative_eq!(reversed.sample(t), gradient.sample(1.0 - t));
+        }
+
+        // Mirrored across the domain rather than around 0
+        let mut offset = Gradient(vec![
+            (0.5, Colour::solid(1.0, 0.0, 0.0)),
+            (0.7, Colour::solid(0.0, 1.0, 0.0)),
+            (0.8, Colour::solid(0.0, 0.0, 1.0)),
+        ]);
+        offset.reverse();
+        assert!(offset.0.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+        assert_relative_eq!(offset.0[0].0, 0.5);
+        assert_relative_eq!(offset.0[1].0, 0.6);
+        assert_relative_eq!(offset.0[2].0, 0.8);
+        assert_relative_eq!(offset.sample(0.55), Colour::solid(0.0, 0.5, 0.5));
+
+        let mut empty = Gradient(vec![]);
+        empty.reverse();
+        assert!(empty.0.is_empty());
+    }
+
+    #[test]
+    pub fn domain_test() {
+        // The galaxy gradient from the crate tests, sampled over noise in [-1, 1]
+        let galaxy_gradient = Gradient(vec![
+            (-1f64, Colour::solid(0f64, 0f64, 0.02f64)),
+            (-0.1f64, Colour::solid(0.04f64, 0.04f64, 0.08f64)),
+            (0.3f64, Colour::solid(0.1f64, 0.08f64, 0.24f64)),
+            (0.6f64, Colour::solid(0.20f64, 0.08f64, 0.45f64)),
+            (0.75f64, Colour::solid(0.40f64, 0.12f64, 0.55f64)),
+            (0.7f64, Colour::solid(0.55f64, 0.20f64, 0.65f64)),
+            (0.9f64, Colour::solid(0.65f64, 0.30f64, 0.75f64)),
+            (1f64, Colour::solid(0.65f64, 0.40f64, 0.80f64)),
+        ]);
+        assert_eq!(galaxy_gradient.domain(), (-1.0, 1.0));
+
+        let normalised = galaxy_gradient.normalised_domain();
+        assert_eq!(normalised.domain(), (0.0, 1.0));
+        assert_eq!(normalised.0.len(), galaxy_gradient.0.len());
+        for (&(t, colour), &(original_t, original_colour)) in
+            normalised.0.iter().zip(galaxy_gradient.0.iter())
+        {
+            assert_relative_eq!(t, (original_t + 1.0) / 2.0);
+            assert_eq!(colour, original_colour);
+        }
+        for i in 0..=20 {
+            let t = i as f64 / 10.0 - 1.0;
+            assert_relative_eq!(
+                normalised.sample((t + 1.0) / 2.0),
+                galaxy_gradient.sample(t)
+            );
+        }
+
+        let single = Gradient(vec![(0.4, Colour::red(1.0))]);
+        assert_eq!(single.domain(), (0.4, 0.4));
+        assert_eq!(single.normalised_domain().0, single.0);
+
+        assert_eq!(Gradient(vec![]).domain(), (0.0, 0.0));
+        assert!(Gradient(vec![]).normalised_domain().0.is_empty());
+    }
+
+    #[test]
+    pub fn map_positions_test() {
+        let gradient = Gradient(vec![
+            (0.0, Colour::solid(1.0, 0.0, 0.0)),
+            (0.5, Colour::solid(0.0, 1.0, 0.0)),
+            (1.0, Colour::solid(0.0, 0.0, 1.0)),
+        ]);
+        let squared = gradient.map_positions(|t| t * t);
+        assert_eq!(
+            squared.0,
+            vec![
+                (0.0, Colour::solid(1.0, 0.0, 0.0)),
+                (0.25, Colour::solid(0.0, 1.0, 0.0)),
+                (1.0, Colour::solid(0.0, 0.0, 1.0)),
+            ]
+        );
+        // The stop colours now land at t², segments are still linear between them
+        for &(t, colour) in gradient.0.iter() {
+            assert_relative_eq!(squared.sample(t * t), colour);
+        }
+        assert_relative_eq!(squared.sample(0.125), gradient.sample(0.25));
+        assert_relative_eq!(squared.sample(0.625), gradient.sample(0.75));
+
+        // Squaring isn't monotonic over [-1, 1] so the stops are re-sorted
+        let symmetric = Gradient(vec![
+            (-1.0, Colour::solid(1.0, 0.0, 0.0)),
+            (-0.5, Colour::solid(0.0, 1.0, 0.0)),
+            (1.0, Colour::solid(0.0, 0.0, 1.0)),
+        ])
+        .map_positions(|t| t * t);
+        assert_eq!(
+            symmetric.0,
+            vec![
+                (0.25, Colour::solid(0.0, 1.0, 0.0)),
+                (1.0, Colour::solid(1.0, 0.0, 0.0)),
+                (1.0, Colour::solid(0.0, 0.0, 1.0)),
+            ]
+        );
+    }
+
+    #[test]
+    pub fn easing_test() {
+        use strum::IntoEnumIterator;
+
+        let gradient = Gradient(vec![
+            (0.5, Colour::solid(1.0, 0.0, 0.0)),
+            (0.7, Colour::solid(0.0, 1.0, 0.0).with_alpha(0.5)),
+            (0.8, Colour::solid(0.0, 0.0, 1.0)),
+        ]);
+        for i in 0..=40 {
+            let t = i as f64 / 40.0;
+            assert_eq!(gradient.sample_eased(t, Easing::Linear), gradient.sample(t));
+        }
+
+        // SmoothStep is symmetric so the middle of a segment is untouched
+        assert_relative_eq!(
+            gradient.sample_eased(0.6, Easing::SmoothStep),
+            gradient.sample(0.6)
+        );
+        assert_relative_eq!(
+            gradient.sample_eased(0.75, Easing::SmoothStep),
+            Colour::solid(0.0, 0.5, 0.5).with_alpha(0.75)
+        );
+
+        for easing in Easing::iter() {
+            assert_relative_eq!(easing.apply(0.0), 0.0);
+            assert_relative_eq!(easing.apply(1.0), 1.0);
+            // Every stop is still hit exactly
+            for &(t, colour) in gradient.0.iter() {
+                assert_relative_eq!(gradient.sample_eased(t, easing), colour);
+            }
+        }
+        assert!(Easing::EaseIn.apply(0.25) < 0.25);
+        assert!(Easing::EaseOut.apply(0.25) > 0.25);
+        assert_relative_eq!(Easing::EaseInOut.apply(0.5), 0.5);
+    }
+
+    #[test]
+    pub fn sample_many_test() {
+        let galaxy_gradient = Gradient(vec![
+            (-1f64, Colour::solid(0f64, 0f64, 0.02f64)),
+            (-0.1f64, Colour::solid(0.04f64, 0.04f64, 0.08f64)),
+            (0.3f64, Colour::solid(0.1f64, 0.08f64, 0.24f64)),
+            (0.6f64, Colour::solid(0.20f64, 0.08f64, 0.45f64)),
+            (0.75f64, Colour::solid(0.40f64, 0.12f64, 0.55f64)),
+            (0.7f64, Colour::solid(0.55f64, 0.20f64, 0.65f64)),
+            (0.9f64, Colour::solid(0.65f64, 0.30f64, 0.75f64)),
+            (
+                1f64,
+                Colour::solid(0.65f64, 0.40f64, 0.80f64).with_alpha(0.5),
+            ),
+        ]);
+        // Unsorted, out of range, exactly on stops, repeated and NaN
+        let mut ts: Vec<f64> = (0..500)
+            .map(|i| ((i * 7919) % 500) as f64 / 200.0 - 1.25)
+            .collect();
+        ts.extend(galaxy_gradient.0.iter().map(|(t, _)| *t));
+        ts.extend([
+            0.3,
+            -0.0,
+            0.0,
+            f64::NAN,
+            -f64::NAN,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            0.3,
+        ]);
+
+        let batched = galaxy_gradient.sample_many(&ts);
+        assert_eq!(batched.len(), ts.len());
+        for (t, colour) in ts.iter().zip(batched) {
+            let expected = galaxy_gradient.sample(*t);
+            assert!(
+                expected.all_rgba_with(colour, |a, b| a.to_bits() == b.to_bits()),
+                "{t}: {expected:?} != {colour:?}"
+            );
+        }
+
+        // A stop with a NaN position is skipped over the same way
+        let nan_stop = Gradient(vec![
+            (0.0, Colour::red(1.0)),
+            (f64::NAN, Colour::green(1.0)),
+            (1.0, Colour::blue(1.0)),
+        ]);
+        let ts = [-0.5, 0.25, 0.75, 1.5];
+        let batched = nan_stop.sample_many(&ts);
+        for (t, colour) in ts.iter().zip(batched) {
+            let expected = nan_stop.sample(*t);
+            assert!(expected.all_rgba_with(colour, |a, b| a.to_bits() == b.to_bits()));
+        }
+
+        let single = Gradient(vec![(0.5, Colour::red(1.0))]);
+        assert_eq!(single.sample_many(&[0.0, 1.0]), vec![Colour::red(1.0); 2]);
+        assert!(Gradient(vec![]).sample_many(&[]).is_empty());
+    }
+
+    #[test]
+    pub fn lut_test() {
+        let gradient = Gradient(vec![
+            (-0.5, Colour::solid(1.0, 0.0, 0.0)),
+            (0.1, Colour::solid(0.0, 1.0, 0.0).with_alpha(0.5)),
+            (0.3, Colour::solid(0.0, 0.2, 1.0)),
+            (1.5, Colour::grey(1.0)),
+        ]);
+        let domain = gradient.domain();
+        let lut = gradient.as_lut(256);
+        assert_eq!(lut.len(), 256);
+        assert_eq!(lut[0], gradient.sample(-0.5));
+        assert_eq!(lut[255], gradient.sample(1.5));
+        for i in 0..=1000 {
+            let t = -0.6 + 2.2 * i as f64 / 1000.0;
+            assert_relative_eq!(
+                lut_sample(&lut, t, domain),
+                gradient.sample(t),
+                epsilon = 1e-2
+            );
+        }
+        // Exact on the baked entries
+        for (i, &colour) in lut.iter().enumerate() {
+            let t = -0.5 + 2.0 * i as f64 / 255.0;
+            assert_relative_eq!(lut_sample(&lut, t, domain), colour, epsilon = 1e-12);
+        }
+
+        // The stack array matches the baked table
+        let array = gradient.sample_array::<16>();
+        assert_eq!(array.to_vec(), gradient.as_lut(16));
+        assert_eq!(
+            gradient.sample_array::<2>(),
+            [gradient.sample(-0.5), gradient.sample(1.5)]
+        );
+        assert_eq!(gradient.sample_array::<1>(), [gradient.sample(-0.5)]);
+        assert_eq!(gradient.sample_array::<0>(), []);
+        let samples: Vec<(f64, Colour)> = gradient.samples(256).collect();
+        assert_eq!(samples.len(), 256);
+        assert_eq!(samples[0], (-0.5, gradient.sample(-0.5)));
+        assert_eq!(samples[255], (1.5, gradient.sample(1.5)));
+        assert_eq!(
+            samples
+                .iter()
+                .map(|&(_, colour)| colour)
+                .collect::<Vec<_>>(),
+            lut
+        );
+        assert_eq!(
+            gradient.samples(1).collect::<Vec<_>>(),
+            vec![(-0.5, gradient.sample(-0.5))]
+        );
+        assert_eq!(gradient.samples(0).count(), 0);
+        let black_to_white = Gradient(vec![(0.0, Colour::grey(0.0)), (1.0, Colour::grey(1.0))]);
+        assert_eq!(
+            black_to_white.sample_colours(3).collect::<Vec<_>>(),
+            vec![Colour::grey(0.0), Colour::grey(0.5), Colour::grey(1.0)]
+        );
+        assert_eq!(
+            black_to_white.sample_colours(2).collect::<Vec<_>>(),
+            vec![Colour::grey(0.0), Colour::grey(1.0)]
+        );
+        assert_eq!(
+            gradient.sample_colours(1).collect::<Vec<_>>(),
+            vec![gradient.sample(-0.5)]
+        );
+        assert_eq!(gradient.sample_colours(0).count(), 0);
+        assert_eq!(gradient.sample_colours(256).collect::<Vec<_>>(), lut);
+
+        assert!(gradient.as_lut(0).is_empty());
+        assert_eq!(gradient.as_lut(1), vec![gradient.sample(-0.5)]);
+        assert_eq!(
+            lut_sample(&gradient.as_lut(1), 0.7, domain),
+            gradient.sample(-0.5)
+        );
+        let single = Gradient(vec![(0.5, Colour::red(1.0))]);
+        assert_eq!(
+            lut_sample(&single.as_lut(4), 0.9, single.domain()),
+            Colour::red(1.0)
+        );
+    }
+
+    #[test]
+    pub fn lut_linear_test() {
+        let gradient = Gradient(vec![
+            (0.0, Colour::grey(0.0)),
+            (1.0, Colour::grey(1.0).with_alpha(0.0)),
+        ]);
+        let naive: Vec<[u8; 4]> = gradient.as_lut(5).into_iter().map(Into::into).collect();
+        let linear: [[u8; 4]; 5] = gradient.as_lut_linear(5).try_into().unwrap();
+
+        assert_eq!(linear[0], naive[0]);
+        assert_eq!(linear[4], naive[4]);
+        // Half the light of white is encoded brighter than 0.5
+        assert_eq!(linear[2], [188, 188, 188, 128]);
+        assert_ne!(linear[2], naive[2]);
+        assert!(linear[1][0] > naive[1][0] && linear[3][0] > naive[3][0]);
+        // Alpha stays linear
+        assert_eq!(linear.map(|texel| texel[3]), [255, 191, 128, 64, 0]);
+
+        assert!(gradient.as_lut_linear(0).is_empty());
     }
 
-    /// Select the lower bound colour, use `.select_upper()` for the upper
-    /// bound colour
-    pub fn select(&self, t: f64) -> Colour {
-        self.subgradient(t).0.1
+    #[test]
+    pub fn validate_test() {
+        let healthy = Gradient(vec![
+            (0.0, Colour::transparent()),
+            (0.5, Colour::red(1.0)),
+            (0.5 + 1e-7, Colour::red(1.0)),
+            (1.0, Colour::grey(1.0)),
+        ]);
+        assert!(healthy.validate().is_empty());
+        assert!(Gradient(vec![]).validate().is_empty());
+
+        let unsorted = Gradient(vec![
+            (0.0, Colour::grey(0.0)),
+            (0.75, Colour::grey(0.5)),
+            (0.7, Colour::grey(0.6)),
+            (1.0, Colour::grey(1.0)),
+        ]);
+        assert_eq!(unsorted.validate(), vec![GradientIssue::Unsorted(2)]);
+
+        let nan = Gradient(vec![
+            (0.0, Colour::grey(0.0)),
+            (f64::NAN, Colour::grey(0.5)),
+            (1.0, Colour::grey(1.0)),
+        ]);
+        assert_eq!(nan.validate(), vec![GradientIssue::NanPosition(1)]);
+
+        let duplicate = Gradient(vec![
+            (0.0, Colour::grey(0.0)),
+            (0.5, Colour::grey(0.0)),
+            (0.5, Colour::grey(1.0)),
+        ]);
+        assert_eq!(
+            duplicate.validate(),
+            vec![GradientIssue::DuplicatePosition(2)]
+        );
+
+        let out_of_range = Gradient(vec![
+            (0.0, Colour::grey(-0.1)),
+            (0.5, Colour::grey(0.5).with_alpha(1.5)),
+            (1.0, Colour::grey(f64::NAN)),
+        ]);
+        assert_eq!(
+            out_of_range.validate(),
+            vec![
+                GradientIssue::OutOfRangeColour(0),
+                GradientIssue::OutOfRangeColour(1),
+                GradientIssue::OutOfRangeColour(2),
+            ]
+        );
+
+        let near_duplicate = Gradient(vec![
+            (0.0, Colour::grey(0.0)),
+            (0.5, Colour::red(1.0)),
+            (0.5 + 1e-9, Colour::blue(1.0)),
+            (1.0, Colour::grey(1.0)),
+        ]);
+        assert_eq!(
+            near_duplicate.validate(),
+            vec![GradientIssue::NearDuplicate(2)]
+        );
     }
 
-    /// Select the upper bound colour, use `.select()` for the lower bound
-    /// colour
-    pub fn select_upper(&self, t: f64) -> Colour {
-        self.subgradient(t).1.1
+    #[test]
+    pub fn quantize_to_palette_test() {
+        let gradient = Gradient(vec![
+            (-1.0, Colour::solid(1.0, 0.0, 0.0)),
+            (0.0, Colour::solid(0.0, 1.0, 0.0)),
+            (1.0, Colour::solid(0.0, 0.0, 1.0)),
+        ]);
+        let palette = [
+            Colour::grey(0.0),
+            Colour::grey(1.0),
+            Colour::red(1.0),
+            Colour::green(1.0),
+            Colour::blue(1.0),
+            Colour::solid(1.0, 1.0, 0.0),
+            Colour::solid(0.0, 1.0, 1.0),
+        ];
+        let stepped = gradient.quantize_to_palette(&palette, 64);
+        assert!(stepped.0.iter().all(|(_, colour)| palette.contains(colour)));
+        assert_eq!(stepped.domain(), gradient.domain());
+        // Only hard edges, so every sample is a palette colour
+        for i in 0..=200 {
+            let t = -1.0 + i as f64 / 100.0;
+            assert!(palette.contains(&stepped.sample(t)), "{t}");
+        }
+        // The stops survive and something lands in between them
+        assert_eq!(stepped.sample(-1.0), Colour::red(1.0));
+        assert_eq!(stepped.sample(0.0), Colour::green(1.0));
+        assert_eq!(stepped.sample(1.0), Colour::blue(1.0));
+        assert!(
+            stepped
+                .0
+                .iter()
+                .any(|(_, colour)| *colour == Colour::solid(1.0, 1.0, 0.0))
+        );
+        assert!(
+            stepped
+                .0
+                .iter()
+                .any(|(_, colour)| *colour == Colour::solid(0.0, 1.0, 1.0))
+        );
+
+        // A single palette colour collapses to a flat gradient
+        let flat = gradient.quantize_to_palette(&[Colour::grey(0.5)], 64);
+        assert_eq!(
+            flat.0,
+            vec![(-1.0, Colour::grey(0.5)), (1.0, Colour::grey(0.5))]
+        );
+        assert_eq!(
+            gradient.quantize_to_palette(&palette, 1).0,
+            vec![(-1.0, Colour::red(1.0))]
+        );
+        assert!(gradient.quantize_to_palette(&palette, 0).0.is_empty());
+        assert!(gradient.quantize_to_palette(&[], 64).0.is_empty());
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use approx::assert_relative_eq;
+    #[test]
+    pub fn edge_policy_test() {
+        use strum::IntoEnumIterator;
 
-    use super::Gradient;
-    use crate::Colour;
+        let gradient = Gradient(vec![
+            (0.25, Colour::grey(0.25).with_alpha(0.5)),
+            (0.5, Colour::grey(0.5).with_alpha(0.75)),
+            (0.75, Colour::solid(0.2, 0.4, 0.6)),
+        ]);
+        // Inside the domain every policy is plain sampling
+        for policy in EdgePolicy::iter() {
+            for i in 0..=10 {
+                let t = 0.25 + i as f64 / 20.0;
+                assert_eq!(gradient.sample_with_policy(t, policy), gradient.sample(t));
+            }
+        }
+
+        let clamp = |t| gradient.sample_with_policy(t, EdgePolicy::Clamp);
+        assert_eq!(clamp(0.0), Colour::grey(0.25).with_alpha(0.5));
+        assert_eq!(clamp(1.0), Colour::solid(0.2, 0.4, 0.6));
+
+        let transparent = |t| gradient.sample_with_policy(t, EdgePolicy::Transparent);
+        assert_eq!(transparent(0.2), Colour::transparent());
+        assert_eq!(transparent(0.8), Colour::transparent());
+
+        let extrapolate = |t| gradient.sample_with_policy(t, EdgePolicy::Extrapolate);
+        assert_relative_eq!(extrapolate(0.125), Colour::grey(0.125).with_alpha(0.375));
+        assert_relative_eq!(extrapolate(-1.0), Colour::grey(0.0).with_alpha(0.0));
+        assert_relative_eq!(extrapolate(0.875), Colour::new(0.05, 0.35, 0.65, 1.0));
+        assert_relative_eq!(extrapolate(2.0), Colour::new(0.0, 0.0, 1.0, 1.0));
+        // Matches extending the gradient first
+        let mut extended = Gradient(gradient.0.clone());
+        extended.extend(0.125, 0.875);
+        assert_relative_eq!(extrapolate(0.125), extended.sample(0.125));
+        assert_relative_eq!(extrapolate(0.875), extended.sample(0.875));
+
+        let single = Gradient(vec![(0.5, Colour::red(1.0))]);
+        assert_eq!(
+            single.sample_with_policy(0.0, EdgePolicy::Extrapolate),
+            Colour::red(1.0)
+        );
+        assert_eq!(
+            single.sample_with_policy(1.0, EdgePolicy::Transparent),
+            Colour::transparent()
+        );
+    }
 
     #[test]
-    pub fn subgradient_test() {
+    pub fn policy_gradient_test() {
+        use strum::IntoEnumIterator;
+
+        let gradient = Gradient(vec![
+            (0.25, Colour::grey(0.25).with_alpha(0.5)),
+            (0.5, Colour::grey(0.5).with_alpha(0.75)),
+            (0.75, Colour::solid(0.2, 0.4, 0.6)),
+        ]);
+        let mut policed = PolicyGradient::new(gradient.clone(), EdgePolicy::Clamp);
+        for policy in EdgePolicy::iter() {
+            policed.set_edge_policy(policy);
+            assert_eq!(policed.policy, policy);
+            for t in [-1.0, 0.0, 0.3, 0.5, 0.7, 0.8, 2.0] {
+                assert_eq!(policed.sample(t), gradient.sample_with_policy(t, policy));
+            }
+        }
+
+        let overlay = gradient.clone().with_edge_policy(EdgePolicy::Transparent);
+        assert_eq!(overlay.sample(0.0), Colour::transparent());
+        assert_eq!(overlay.sample(1.0), Colour::transparent());
+        assert_eq!(overlay.sample(0.5), gradient.sample(0.5));
+        let extended = gradient.clone().with_edge_policy(EdgePolicy::Extrapolate);
+        assert_relative_eq!(
+            extended.sample(0.125),
+            Colour::grey(0.125).with_alpha(0.375)
+        );
+        let clamped = gradient.clone().with_edge_policy(EdgePolicy::Clamp);
+        assert_eq!(clamped.sample(2.0), gradient.sample(2.0));
+        assert_eq!(clamped.gradient, gradient);
+    }
+
+    #[test]
+    pub fn wrap_mode_test() {
+        use strum::IntoEnumIterator;
+
+        let gradient = Gradient(vec![
+            (0.25, Colour::grey(0.25).with_alpha(0.5)),
+            (0.5, Colour::grey(0.5).with_alpha(0.75)),
+            (0.75, Colour::solid(0.2, 0.4, 0.6)),
+        ]);
+        let (t_min, t_max) = gradient.domain();
+        let len = t_max - t_min;
+        // Every mode leaves the inside of the domain alone
+        for mode in WrapMode::iter() {
+            for i in 0..10 {
+                let t = t_min + i as f64 / 10.0 * len;
+                assert_relative_eq!(gradient.sample_wrapped(t, mode), gradient.sample(t));
+            }
+        }
+        for t in [-1.0, 0.0, 0.8, 2.0] {
+            assert_eq!(
+                gradient.sample_wrapped(t, WrapMode::Clamp),
+                gradient.sample(t)
+            );
+        }
+
+        let repeat = |t| gradient.sample_wrapped(t, WrapMode::Repeat);
+        assert_relative_eq!(
+            repeat(t_max + 0.1 * len),
+            gradient.sample(t_min + 0.1 * len)
+        );
+        assert_relative_eq!(
+            repeat(t_min - 0.1 * len),
+            gradient.sample(t_max - 0.1 * len)
+        );
+        assert_relative_eq!(
+            repeat(t_max + 3.4 * len),
+            gradient.sample(t_min + 0.4 * len)
+        );
+        assert_relative_eq!(repeat(t_max), gradient.sample(t_min));
+
+        let mirror = |t| gradient.sample_wrapped(t, WrapMode::Mirror);
+        assert_relative_eq!(
+            mirror(t_max + 0.1 * len),
+            gradient.sample(t_max - 0.1 * len)
+        );
+        assert_relative_eq!(
+            mirror(t_min - 0.1 * len),
+            gradient.sample(t_min + 0.1 * len)
+        );
+        assert_relative_eq!(mirror(t_max + len), gradient.sample(t_min));
+        assert_relative_eq!(
+            mirror(t_max + 1.25 * len),
+            gradient.sample(t_min + 0.25 * len)
+        );
+
+        let single = Gradient(vec![(0.5, Colour::red(1.0))]);
+        for mode in WrapMode::iter() {
+            assert_eq!(single.sample_wrapped(2.0, mode), Colour::red(1.0));
+        }
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    pub fn to_image_test() {
+        use image::Rgba;
+
+        let gradient = Gradient(vec![(-1.0, Colour::grey(0.0)), (1.0, Colour::grey(1.0))]);
+        let (black, white) = (Rgba([0, 0, 0, 255]), Rgba([255, 255, 255, 255]));
+
+        let image = gradient.to_image(64, 4);
+        assert_eq!(image.dimensions(), (64, 4));
+        for y in 0..4 {
+            assert_eq!(*image.get_pixel(0, y), black);
+            assert_eq!(*image.get_pixel(63, y), white);
+        }
+        assert!(image.get_pixel(31, 0).0[0] < image.get_pixel(32, 0).0[0]);
+
+        let image = gradient.to_image_vertical(4, 64);
+        assert_eq!(image.dimensions(), (4, 64));
+        for x in 0..4 {
+            assert_eq!(*image.get_pixel(x, 0), black);
+            assert_eq!(*image.get_pixel(x, 63), white);
+        }
+
+        // Nothing is sampled for an empty image, even without stops
+        assert_eq!(Gradient(vec![]).to_image(0, 8).dimensions(), (0, 8));
+        assert_eq!(
+            Gradient(vec![]).to_image_vertical(0, 8).dimensions(),
+            (0, 8)
+        );
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    pub fn from_image_row_test() {
+        use super::GradientError;
+        use image::{Rgba, RgbaImage};
+
+        let mut image = RgbaImage::new(2, 2);
+        image.put_pixel(0, 1, Rgba([255, 0, 0, 255]));
+        image.put_pixel(1, 1, Rgba([0, 0, 255, 128]));
+
+        let gradient = Gradient::from_image_row(&image, 1, 2).unwrap();
+        assert_eq!(
+            gradient.0,
+            vec![
+                (0.0, Colour::red(1.0)),
+                (1.0, Colour::from_u8_rgba(0, 0, 255, 128))
+            ]
+        );
+        let gradient = Gradient::from_image_row(&image, 1, 5).unwrap();
+        assert_eq!(gradient.0.len(), 5);
+        assert_eq!(gradient.domain(), (0.0, 1.0));
+        assert_eq!(gradient.0[1].1, Colour::red(1.0));
+        assert_eq!(gradient.0[3].1, Colour::from_u8_rgba(0, 0, 255, 128));
+        assert!(gradient.validate().is_empty());
+        assert_eq!(
+            Gradient::from_image_row(&image, 0, 2).unwrap().0[0].1,
+            Colour::transparent()
+        );
+
+        for stops in [0, 1] {
+            assert_eq!(
+                Gradient::from_image_row(&image, 1, stops).err(),
+                Some(GradientError::TooFewStops(stops))
+            );
+        }
+        assert_eq!(
+            Gradient::from_image_row(&image, 2, 2).err(),
+            Some(GradientError::InvalidRow(2))
+        );
+        assert_eq!(
+            Gradient::from_image_row(&RgbaImage::new(0, 4), 0, 2).err(),
+            Some(GradientError::InvalidRow(0))
+        );
+    }
+
+    #[test]
+    pub fn sample_over_test() {
+        let gradient = Gradient(vec![
+            (0.0, Colour::red(1.0).with_alpha(0.0)),
+            (0.5, Colour::red(1.0).with_alpha(0.5)),
+            (1.0, Colour::red(1.0)),
+        ]);
+        let white = Colour::grey(1.0);
+        assert_relative_eq!(gradient.sample_over(0.0, white), white);
+        assert_relative_eq!(
+            gradient.sample_over(0.5, white),
+            Colour::solid(1.0, 0.5, 0.5)
+        );
+        assert_relative_eq!(gradient.sample_over(1.0, white), Colour::red(1.0));
+        for i in 0..=10 {
+            let t = i as f64 / 10.0;
+            assert_eq!(
+                gradient.sample_over(t, white),
+                white.compose(gradient.sample(t))
+            );
+        }
+
+        // Over a translucent background the alphas combine
+        let sample = gradient.sample_over(0.5, Colour::blue(1.0).with_alpha(0.5));
+        assert_relative_eq!(sample.a, 0.75);
+    }
+
+    #[test]
+    pub fn sample_oklab_test() {
+        let gradient = Gradient(vec![
+            (0.0, Colour::red(1.0)),
+            (0.5, Colour::green(1.0).with_alpha(0.5)),
+            (1.0, Colour::blue(1.0)),
+        ]);
+        for (t, colour) in &gradient.0 {
+            assert_relative_eq!(gradient.sample_oklab(*t), *colour, epsilon = 1e-5);
+        }
+        assert_relative_eq!(
+            gradient.sample_oklab(-1.0),
+            Colour::red(1.0),
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(
+            gradient.sample_oklab(2.0),
+            Colour::blue(1.0),
+            epsilon = 1e-6
+        );
+
+        // The RGB midpoint of red and green is a dark, muddy olive
+        let (rgb, oklab) = (gradient.sample(0.25), gradient.sample_oklab(0.25));
+        assert_relative_eq!(rgb, Colour::new(0.5, 0.5, 0.0, 0.75));
+        assert!((oklab.r - rgb.r).abs() > 0.1 && (oklab.g - rgb.g).abs() > 0.1);
+        assert_relative_eq!(oklab.a, 0.75);
+        let (l, _, _) = oklab.to_oklab();
+        let (l_red, _, _) = Colour::red(1.0).to_oklab();
+        let (l_green, _, _) = Colour::green(1.0).to_oklab();
+        assert_relative_eq!(l, (l_red + l_green) / 2.0, epsilon = 1e-6);
+        assert!(l > rgb.to_oklab().0);
+    }
+
+    #[test]
+    pub fn sample_linear_test() {
+        let gradient = Gradient(vec![
+            (0.0, Colour::grey(0.0)),
+            (1.0, Colour::grey(1.0).with_alpha(0.0)),
+        ]);
+        let (gamma, linear) = (gradient.sample(0.5), gradient.sample_linear(0.5));
+        assert_relative_eq!(gamma, Colour::grey(0.5).with_alpha(0.5));
+        assert_relative_eq!(linear.r, 0.735, epsilon = 1e-3);
+        assert!(linear.r > gamma.r);
+        assert_relative_eq!(linear.a, 0.5);
+        // Half of the light either way
+        assert_relative_eq!(
+            linear.to_linear(),
+            Colour::grey(0.5).with_alpha(0.5),
+            epsilon = 1e-12
+        );
+
+        for (t, colour) in &gradient.0 {
+            assert_relative_eq!(gradient.sample_linear(*t), *colour, epsilon = 1e-12);
+        }
+        assert_relative_eq!(gradient.sample_linear(-1.0), Colour::grey(0.0));
+        assert_relative_eq!(
+            gradient.sample_linear(2.0),
+            Colour::grey(1.0).with_alpha(0.0),
+            epsilon = 1e-12
+        );
+        // ...and the same texels as the linear LUT
+        for (i, texel) in gradient.as_lut_linear(5).into_iter().enumerate() {
+            let (r, g, b, a) = gradient
+                .sample_linear(i as f64 / 4.0)
+                .to_u8_rgba_saturating();
+            assert_eq!(texel, [r, g, b, a]);
+        }
+    }
+
+    #[test]
+    pub fn sample_straight_test() {
+        let gradient = Gradient(vec![(0.0, Colour::red(1.0)), (1.0, Colour::transparent())]);
+        let (darkened, straight) = (gradient.sample(0.5), gradient.sample_straight(0.5));
+        assert_relative_eq!(darkened, Colour::new(0.5, 0.0, 0.0, 0.5));
+        assert_relative_eq!(straight, Colour::red(1.0).with_alpha(0.5));
+        for i in 0..10 {
+            let t = i as f64 / 10.0;
+            assert_relative_eq!(
+                gradient.sample_straight(t),
+                Colour::red(1.0).with_alpha(1.0 - t)
+            );
+        }
+        // Nothing to un-premultiply once it is fully transparent
+        assert_relative_eq!(gradient.sample_straight(1.0), Colour::transparent());
+        assert_relative_eq!(gradient.sample_straight(2.0), Colour::transparent());
+
+        let gradient = Gradient(vec![
+            (0.0, Colour::red(1.0).with_alpha(0.25)),
+            (0.5, Colour::blue(1.0).with_alpha(0.75)),
+            (1.0, Colour::green(1.0)),
+        ]);
+        for (t, colour) in &gradient.0 {
+            assert_relative_eq!(gradient.sample_straight(*t), *colour, epsilon = 1e-12);
+        }
+        // The more opaque blue outweighs red
+        assert_relative_eq!(
+            gradient.sample_straight(0.25),
+            Colour::new(0.25, 0.0, 0.75, 0.5),
+            epsilon = 1e-12
+        );
+        // ...and opaque stops interpolate as `.sample()` does
+        let opaque = gradient.map(|colour| colour.with_alpha(1.0));
+        assert_relative_eq!(
+            opaque.sample_straight(0.3),
+            opaque.sample(0.3),
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    pub fn sample_catmull_rom_test() {
+        // Stops on a straight line, however they are spaced
+        let collinear = Gradient(vec![
+            (0.0, Colour::new(0.0, 1.0, 0.2, 1.0)),
+            (0.1, Colour::new(0.1, 0.9, 0.2, 0.9)),
+            (0.4, Colour::new(0.4, 0.6, 0.2, 0.6)),
+            (0.5, Colour::new(0.5, 0.5, 0.2, 0.5)),
+            (1.0, Colour::new(1.0, 0.0, 0.2, 0.0)),
+        ]);
+        for i in -5..=25 {
+            let t = i as f64 / 20.0;
+            assert_relative_eq!(
+                collinear.sample_catmull_rom(t),
+                collinear.sample(t),
+                epsilon = 1e-12
+            );
+        }
+
+        let gradient = Gradient(vec![
+            (0.0, Colour::new(1.0, 0.0, 0.0, 1.0)),
+            (0.3, Colour::new(0.2, 1.0, 0.0, 0.5)),
+            (0.5, Colour::new(0.0, 0.4, 1.0, 1.0)),
+            (1.0, Colour::new(0.9, 0.9, 0.1, 0.2)),
+        ]);
+        for (t, colour) in &gradient.0 {
+            assert_relative_eq!(gradient.sample_catmull_rom(*t), *colour, epsilon = 1e-12);
+        }
+        assert_eq!(gradient.sample_catmull_rom(-1.0), gradient.0[0].1);
+        assert_eq!(gradient.sample_catmull_rom(2.0), gradient.0[3].1);
+        assert_ne!(gradient.sample_catmull_rom(0.4), gradient.sample(0.4));
+        // No kink at the inner stops, unlike `.sample()`
+        let h = 1e-6;
+        for t in [0.3, 0.5] {
+            let before = (gradient.sample_catmull_rom(t) - gradient.sample_catmull_rom(t - h)) / h;
+            let after = (gradient.sample_catmull_rom(t + h) - gradient.sample_catmull_rom(t)) / h;
+            assert_relative_eq!(before, after, epsilon = 1e-4);
+            let linear_before = (gradient.sample(t) - gradient.sample(t - h)) / h;
+            let linear_after = (gradient.sample(t + h) - gradient.sample(t)) / h;
+            assert!(linear_before.distance(&linear_after) > 1.0);
+        }
+
+        // Too few stops for a spline
+        let three = Gradient(gradient.0[..3].to_vec());
+        for i in 0..=10 {
+            let t = i as f64 / 20.0;
+            assert_eq!(three.sample_catmull_rom(t), three.sample(t));
+        }
+    }
+
+    #[test]
+    pub fn rainbow_test() {
+        for stops in [2, 3, 7, 13] {
+            let rainbow = Gradient::rainbow(stops);
+            assert_eq!(rainbow.0.len(), stops);
+            assert_eq!(rainbow.domain(), (0.0, 1.0));
+            assert!(rainbow.validate().is_empty());
+            // Both ends are the same hue for a seamless wrap
+            assert_relative_eq!(rainbow.sample(0.0), rainbow.sample(1.0), epsilon = 1e-12);
+            assert_relative_eq!(rainbow.sample(0.0).to_hsl().0, 0.0, epsilon = 1e-9);
+            for (i, &(t, colour)) in rainbow.0.iter().enumerate() {
+                let (h, s, l) = colour.to_hsl();
+                assert_relative_eq!(t, i as f64 / (stops - 1) as f64);
+                assert_relative_eq!(h, (360.0 * t) % 360.0, epsilon = 1e-9);
+                assert_relative_eq!(s, 1.0, epsilon = 1e-12);
+                assert_relative_eq!(l, 0.5, epsilon = 1e-12);
+            }
+        }
+        // Every 60 degrees the linear samples are the hue wheel itself
+        let rainbow = Gradient::rainbow(7);
+        for i in 0..=36 {
+            let t = i as f64 / 36.0;
+            assert_relative_eq!(
+                rainbow.sample(t),
+                Colour::from_hsl(360.0 * t, 1.0, 0.5),
+                epsilon = 1e-9
+            );
+        }
+
+        assert!(Gradient::rainbow(0).0.is_empty());
+        assert_eq!(Gradient::rainbow(1).0, vec![(0.0, Colour::red(1.0))]);
+    }
+
+    #[test]
+    pub fn with_domain_test() {
         let gradient = Gradient(vec![
+            (0.0, Colour::solid(1.0, 0.0, 0.0)),
+            (0.25, Colour::solid(0.0, 1.0, 0.0)),
+            (1.0, Colour::solid(0.0, 0.0, 1.0)),
+        ]);
+        let rescaled = Gradient(gradient.0.clone()).with_domain(-1.0, 1.0);
+        assert_eq!(rescaled.domain(), (-1.0, 1.0));
+        assert_eq!(
+            rescaled.0.iter().map(|&(t, _)| t).collect::<Vec<_>>(),
+            vec![-1.0, -0.5, 1.0]
+        );
+        for i in 0..=10 {
+            let t = i as f64 / 10.0;
+            assert_relative_eq!(
+                rescaled.sample(2.0 * t - 1.0),
+                gradient.sample(t),
+                epsilon = 1e-12
+            );
+        }
+        // Back again is the original
+        assert_eq!(rescaled.with_domain(0.0, 1.0).0, gradient.0);
+
+        let single = Gradient(vec![(0.5, Colour::red(1.0))]).with_domain(-1.0, 1.0);
+        assert_eq!(single.0, vec![(-1.0, Colour::red(1.0))]);
+        assert!(Gradient(vec![]).with_domain(-1.0, 1.0).0.is_empty());
+    }
+
+    #[test]
+    pub fn random_test() {
+        use rand::{SeedableRng, rngs::StdRng};
+
+        let mut rng = StdRng::seed_from_u64(0xbeef);
+        for n in [2, 5, 100] {
+            let gradient = Gradient::random(&mut rng, n);
+            assert_eq!(gradient.0.len(), n);
+            assert!(gradient.0.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+            for &(t, colour) in &gradient.0 {
+                assert!((0.0..=1.0).contains(&t));
+                assert_eq!(colour.a, 1.0);
+            }
+        }
+        assert!(Gradient::random(&mut rng, 0).0.is_empty());
+        assert_eq!(Gradient::random(&mut rng, 1).0.len(), 1);
+
+        let (mut a, mut b) = (StdRng::seed_from_u64(7), StdRng::seed_from_u64(7));
+        assert_eq!(
+            Gradient::random(&mut a, 10).0,
+            Gradient::random(&mut b, 10).0
+        );
+    }
+
+    #[test]
+    pub fn iter_test() {
+        let mut gradient = Gradient(vec![
             (0.5, Colour::solid(1.0, 0.0, 0.0)),
             (0.7, Colour::solid(0.0, 1.0, 0.0)),
             (0.8, Colour::solid(0.0, 0.0, 1.0)),
         ]);
+        let ts: Vec<f64> = gradient.iter().map(|&(t, _)| t).collect();
+        assert_eq!(ts, vec![0.5, 0.7, 0.8]);
+
+        let mut ts = vec![];
+        for (t, _) in &gradient {
+            ts.push(*t);
+        }
+        assert_eq!(ts, vec![0.5, 0.7, 0.8]);
+
+        for (_, colour) in &mut gradient {
+            *colour = colour.inverted();
+        }
+        gradient.iter_mut().for_each(|(t, _)| *t *= 2.0);
         assert_eq!(
-            gradient.subgradient(0.1),
-            (
-                (0.5, Colour::solid(1.0, 0.0, 0.0)),
-                (0.5, Colour::solid(1.0, 0.0, 0.0))
-            )
+            gradient.into_iter().collect::<Vec<_>>(),
+            vec![
+                (1.0, Colour::solid(0.0, 1.0, 1.0)),
+                (1.4, Colour::solid(1.0, 0.0, 1.0)),
+                (1.6, Colour::solid(1.0, 1.0, 0.0)),
+            ]
+        );
+    }
+
+    #[test]
+    pub fn from_iter_test() {
+        let gradient: Gradient = [1.0, 0.0, 0.5, 0.25]
+            .into_iter()
+            .map(|t| (t, Colour::grey(t)))
+            .collect();
+        let ts: Vec<f64> = gradient.iter().map(|&(t, _)| t).collect();
+        assert_eq!(ts, vec![0.0, 0.25, 0.5, 1.0]);
+        assert!(gradient.validate().is_empty());
+        assert_relative_eq!(gradient.sample(0.75), Colour::grey(0.75));
+        assert_relative_eq!(gradient.sample(0.125), Colour::grey(0.125));
+
+        // Round trips with `into_iter()`
+        let reversed: Gradient = gradient.reversed().into_iter().collect();
+        assert_relative_eq!(reversed.sample(0.25), Colour::grey(0.75));
+        assert!(core::iter::empty().collect::<Gradient>().is_empty());
+    }
+
+    #[test]
+    pub fn concat_test() {
+        let first = Gradient(vec![(0.0, Colour::grey(0.0)), (1.0, Colour::grey(0.5))]);
+        let second = Gradient(vec![(2.0, Colour::red(1.0)), (4.0, Colour::blue(1.0))]);
+
+        let stitched = first.concat(&second, 1.0);
+        assert_eq!(stitched.domain(), (0.0, 3.0));
+        assert!(stitched.0.is_sorted_by(|a, b| a.0 <= b.0));
+        assert_relative_eq!(stitched.sample(0.5), Colour::grey(0.25));
+        assert_relative_eq!(stitched.sample(2.0), Colour::solid(0.5, 0.0, 0.5));
+        // A hard edge at the join, the second gradient takes over
+        assert_eq!(stitched.sample(1.0), Colour::red(1.0));
+        assert_relative_eq!(
+            stitched.sample(1.0 - 1e-9),
+            Colour::grey(0.5),
+            epsilon = 1e-6
         );
         assert_eq!(
-            gradient.subgradient(0.6),
-            (
-                (0.5, Colour::solid(1.0, 0.0, 0.0)),
-                (0.7, Colour::solid(0.0, 1.0, 0.0))
-            )
+            stitched.subgradient(1.0),
+            ((1.0, Colour::red(1.0)), (3.0, Colour::blue(1.0)))
         );
         assert_eq!(
-            gradient.subgradient(0.75),
-            (
+            stitched.subgradient(0.9),
+            ((0.0, Colour::grey(0.0)), (1.0, Colour::grey(0.5)))
+        );
+
+        // Leaving a gap blends across it
+        let gapped = first.concat(&second, 2.0);
+        assert_relative_eq!(gapped.sample(1.5), Colour::solid(0.75, 0.25, 0.25));
+        // Overlapping stops are still sorted
+        let overlapped = first.concat(&second, 0.5);
+        assert!(overlapped.validate().is_empty());
+        assert_eq!(overlapped.domain(), (0.0, 2.5));
+
+        assert_eq!(first.concat(&Gradient(vec![]), 5.0).0, first.0);
+        assert_eq!(Gradient(vec![]).concat(&second, 0.0).domain(), (0.0, 2.0));
+    }
+
+    #[test]
+    pub fn stops_in_range_test() {
+        let gradient = Gradient(vec![
+            (0.5, Colour::solid(1.0, 0.0, 0.0)),
+            (0.7, Colour::solid(0.0, 1.0, 0.0)),
+            (0.8, Colour::solid(0.0, 0.0, 1.0)),
+        ]);
+        assert_eq!(
+            gradient.stops_in_range(0.55, 0.85),
+            &[
                 (0.7, Colour::solid(0.0, 1.0, 0.0)),
                 (0.8, Colour::solid(0.0, 0.0, 1.0))
-            )
+            ]
         );
+        // Inclusive at both ends
+        assert_eq!(gradient.stops_in_range(0.5, 0.7), &gradient.0[..2]);
+        assert_eq!(gradient.stops_in_range(0.7, 0.7), &gradient.0[1..2]);
         assert_eq!(
-            gradient.subgradient(0.9),
-            (
-                (0.8, Colour::solid(0.0, 0.0, 1.0)),
-                (0.8, Colour::solid(0.0, 0.0, 1.0))
-            )
+            gradient.stops_in_range(f64::NEG_INFINITY, f64::INFINITY),
+            &gradient.0[..]
         );
+
+        assert!(gradient.stops_in_range(0.71, 0.79).is_empty());
+        assert!(gradient.stops_in_range(0.9, 1.0).is_empty());
+        assert!(gradient.stops_in_range(0.85, 0.55).is_empty());
+        assert!(gradient.stops_in_range(f64::NAN, 1.0).is_empty());
+        assert!(gradient.stops_in_range(0.0, f64::NAN).is_empty());
+        assert!(Gradient(vec![]).stops_in_range(0.0, 1.0).is_empty());
     }
 
     #[test]
-    pub fn insertion_test() {
+    pub fn map_test() {
         let mut gradient = Gradient(vec![
             (0.5, Colour::solid(1.0, 0.0, 0.0)),
-            (0.7, Colour::solid(0.0, 1.0, 0.0)),
+            (0.7, Colour::solid(0.0, 1.0, 0.0).with_alpha(0.5)),
             (0.8, Colour::solid(0.0, 0.0, 1.0)),
         ]);
-        gradient.insert(0.3, Colour::grey(0.5));
-        assert_eq!(gradient.0.first().unwrap().1, Colour::grey(0.5));
-        gradient.insert(0.9, Colour::grey(0.2));
-        assert_eq!(gradient.0.last().unwrap().1, Colour::grey(0.2));
-        gradient.insert(0.6, Colour::red(0.8));
-        assert_eq!(gradient.0[2].1, Colour::red(0.8));
-        gradient.insert(0.85, Colour::transparent());
-        assert_eq!(gradient.0[5].1, Colour::transparent());
-        assert_eq!(gradient.0[6].1, Colour::grey(0.2));
-        gradient.insert(0.5, Colour::red(1.0).with_blue(1.0));
+        let inverted = gradient.map(|colour| colour.inverted());
         assert_eq!(
-            gradient.0,
+            inverted.0,
             vec![
-                (0.3, Colour::grey(0.5)),
-                (0.5, Colour::red(1.0).with_blue(1.0)),
-                (0.6, Colour::red(0.8)),
-                (0.7, Colour::green(1.0)),
-                (0.8, Colour::blue(1.0)),
-                (0.85, Colour::transparent()),
-                (0.9, Colour::grey(0.2))
+                (0.5, Colour::solid(0.0, 1.0, 1.0)),
+                (0.7, Colour::solid(1.0, 0.0, 1.0).with_alpha(0.5)),
+                (0.8, Colour::solid(1.0, 1.0, 0.0)),
             ]
         );
+
+        gradient.map_colours(|colour| colour.inverted());
+        assert_eq!(gradient.0, inverted.0);
+        gradient.map_colours(|colour| colour.inverted());
+        assert_eq!(gradient.0, inverted.map(|colour| colour.inverted()).0);
+        assert_eq!(gradient.0[0], (0.5, Colour::solid(1.0, 0.0, 0.0)));
     }
 
     #[test]
-    pub fn interpolation_test() {
-        let gradient = Gradient(vec![
+    pub fn invert_test() {
+        let mut gradient = Gradient(vec![
+            (0.5, Colour::new(0.8, 0.3, 0.0, 1.0)),
+            (0.7, Colour::new(0.25, 1.0, 0.6, 0.5)),
+            (0.8, Colour::new(0.0, 0.1, 1.0, 0.0)),
+        ]);
+        let inverted = gradient.inverted();
+        assert_eq!(inverted.0.len(), gradient.0.len());
+        for (&(t, colour), &(inverted_t, inverted_colour)) in gradient.0.iter().zip(&inverted.0) {
+            assert_eq!(inverted_t, t);
+            assert_relative_eq!(inverted_colour.r, 1.0 - colour.r);
+            assert_relative_eq!(inverted_colour.g, 1.0 - colour.g);
+            assert_relative_eq!(inverted_colour.b, 1.0 - colour.b);
+            assert_eq!(inverted_colour.a, colour.a);
+        }
+
+        gradient.invert();
+        assert_eq!(gradient.0, inverted.0);
+        gradient.invert();
+        assert_eq!(gradient.0, inverted.inverted().0);
+        assert_relative_eq!(gradient.0[0].1, Colour::new(0.8, 0.3, 0.0, 1.0));
+    }
+
+    #[test]
+    pub fn max_difference_test() {
+        let reference = Gradient(vec![
             (0.5, Colour::solid(1.0, 0.0, 0.0)),
             (0.7, Colour::solid(0.0, 1.0, 0.0)),
             (0.8, Colour::solid(0.0, 0.0, 1.0)),
         ]);
+        assert_eq!(
+            reference.max_difference(&Gradient(reference.0.clone()), 100),
+            0.0
+        );
 
-        let other_gradient = Gradient(vec![(0.4, Colour::grey(1.0)), (0.6, Colour::transparent())]);
-        assert_eq!(other_gradient.sample(0.5), Colour::grey(0.5).with_alpha(0.5));
-        assert_eq!(gradient.sample(0.6), Colour::solid(0.5, 0.5, 0.0));
-        assert_relative_eq!(other_gradient.sample(0.8), Colour::transparent());
-        assert_eq!(gradient.sample(0.1), Colour::red(1.0));
+        // Nudging one stop's green is the largest difference, at that stop
+        let mut modified = Gradient(reference.0.clone());
+        modified.0[1].1.g = 0.9;
+        assert_relative_eq!(reference.max_difference(&modified, 7), 0.1, epsilon = 1e-9);
+        assert_relative_eq!(modified.max_difference(&reference, 7), 0.1, epsilon = 1e-9);
+        // ...and alpha counts too
+        modified.0[1].1 = modified.0[1].1.with_alpha(0.8);
+        assert_relative_eq!(
+            reference.max_difference(&modified, 7),
+            0.05f64.sqrt(),
+            epsilon = 1e-9
+        );
+
+        // Compared over their own domains
+        let shifted = reference.map_positions(|t| t * 10.0 - 3.0);
+        assert_relative_eq!(
+            reference.max_difference(&shifted, 100),
+            0.0,
+            epsilon = 1e-12
+        );
+        assert_eq!(reference.max_difference(&Gradient(vec![]), 0), 0.0);
     }
 }
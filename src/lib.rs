@@ -1,8 +1,30 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+// `auto_ops` expands to `::std::ops` paths, which `core` also provides
+#[cfg(not(feature = "std"))]
+extern crate core as std;
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+extern crate alloc;
+
 pub mod colour;
+#[cfg(feature = "alloc")]
 pub mod gradient;
+#[cfg(feature = "std")]
+pub mod lut;
+#[cfg(not(feature = "std"))]
+mod math;
+// Needs `alloc` as it uses `Easing`, which lives with `Gradient`
+#[cfg(feature = "alloc")]
+pub mod tween;
 
 pub use colour::*;
+#[cfg(feature = "alloc")]
 pub use gradient::*;
+#[cfg(feature = "std")]
+pub use lut::*;
+#[cfg(feature = "alloc")]
+pub use tween::*;
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,376 @@
+use std::fmt;
+use std::io::{BufRead, BufReader, Read};
+
+use crate::colour::Colour;
+
+/// Errors produced when building or parsing a `Lut3d`
+#[derive(Debug)]
+pub enum LutError {
+    /// Reading the `.cube` file failed
+    Io(std::io::Error),
+    /// A line of the `.cube` file could not be parsed, numbered from 1
+    InvalidLine(usize),
+    /// The `.cube` file never declared a `LUT_3D_SIZE`
+    MissingSize,
+    /// The size was below 2, the smallest grid that can be interpolated,
+    /// or so large that `size³` overflows
+    InvalidSize(usize),
+    /// A channel's domain minimum wasn't below its maximum, or either
+    /// wasn't finite
+    InvalidDomain { min: [f64; 3], max: [f64; 3] },
+    /// The table doesn't hold `size³` entries
+    InvalidEntryCount { expected: usize, found: usize },
+}
+
+impl fmt::Display for LutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LutError::Io(error) => write!(f, "failed to read LUT: {error}"),
+            LutError::InvalidLine(line) => write!(f, "invalid .cube line {line}"),
+            LutError::MissingSize => write!(f, "missing LUT_3D_SIZE"),
+            LutError::InvalidSize(size) => write!(f, "LUT size {size} is below 2 or too large"),
+            LutError::InvalidDomain { min, max } => {
+                write!(f, "LUT domain {min:?} to {max:?} is empty or not finite")
+            }
+            LutError::InvalidEntryCount { expected, found } => {
+                write!(f, "expected {expected} LUT entries but found {found}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for LutError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LutError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for LutError {
+    fn from(error: std::io::Error) -> Self {
+        LutError::Io(error)
+    }
+}
+
+/// A 3D colour lookup table, an `N×N×N` grid of colours that input
+/// colours are trilinearly interpolated into with `Colour::apply_lut3d()`.
+///
+/// Entries are ordered with red changing fastest, then green, then
+/// blue, as in Adobe `.cube` files.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Lut3d {
+    size: usize,
+    table: Vec<Colour>,
+    domain_min: [f64; 3],
+    domain_max: [f64; 3],
+}
+
+/// The `size³` entries of a LUT with `size` grid points along each axis
+fn entry_count(size: usize) -> Result<usize, LutError> {
+    if size < 2 {
+        return Err(LutError::InvalidSize(size));
+    }
+    size.checked_mul(size)
+        .and_then(|s| s.checked_mul(size))
+        .ok_or(LutError::InvalidSize(size))
+}
+
+impl Lut3d {
+    /// Creates a LUT from `size³` entries with a domain of `[0, 1]`
+    pub fn new(size: usize, table: Vec<Colour>) -> Result<Self, LutError> {
+        let expected = entry_count(size)?;
+        if table.len() != expected {
+            return Err(LutError::InvalidEntryCount {
+                expected,
+                found: table.len(),
+            });
+        }
+        Ok(Self {
+            size,
+            table,
+            domain_min: [0f64; 3],
+            domain_max: [1f64; 3],
+        })
+    }
+
+    /// Creates a LUT that maps every colour to itself
+    ///
+    /// # Example
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::{Colour, Lut3d};
+    ///
+    /// let lut = Lut3d::identity(17).unwrap();
+    /// let colour = Colour::new(0.2, 0.4, 0.6, 0.5);
+    /// assert_relative_eq!(colour.apply_lut3d(&lut), colour);
+    /// ```
+    pub fn identity(size: usize) -> Result<Self, LutError> {
+        Self::from_fn(size, Colour::solid)
+    }
+
+    /// Creates a LUT by evaluating `f(r, g, b)` at every grid point
+    pub fn from_fn<F: Fn(f64, f64, f64) -> Colour>(size: usize, f: F) -> Result<Self, LutError> {
+        let expected = entry_count(size)?;
+        let step = (size - 1) as f64;
+        let table = (0..expected)
+            .map(|i| {
+                f(
+                    (i % size) as f64 / step,
+                    (i / size % size) as f64 / step,
+                    (i / (size * size)) as f64 / step,
+                )
+            })
+            .collect();
+        Self::new(size, table)
+    }
+
+    /// Sets the input range each channel is mapped from, inputs
+    /// outside of the domain are clamped to it. Each channel's minimum
+    /// must be below its maximum and both finite, otherwise the inputs
+    /// couldn't be mapped into the grid.
+    pub fn with_domain(
+        mut self,
+        domain_min: [f64; 3],
+        domain_max: [f64; 3],
+    ) -> Result<Self, LutError> {
+        let valid = |i: usize| {
+            domain_min[i].is_finite() && domain_max[i].is_finite() && domain_min[i] < domain_max[i]
+        };
+        if !(0..3).all(valid) {
+            return Err(LutError::InvalidDomain {
+                min: domain_min,
+                max: domain_max,
+            });
+        }
+        self.domain_min = domain_min;
+        self.domain_max = domain_max;
+        Ok(self)
+    }
+
+    /// Parses an Adobe `.cube` file containing a 3D LUT.
+    ///
+    /// `TITLE`, `DOMAIN_MIN` and `DOMAIN_MAX` are understood, comments
+    /// and blank lines are skipped and any other keyword (including
+    /// `LUT_1D_SIZE`) is reported as an `InvalidLine`.
+    ///
+    /// # Example
+    /// ```
+    /// use approx::assert_relative_eq;
+    /// use tcolour::{Colour, Lut3d};
+    ///
+    /// let cube = "
+    /// ## inverts every colour
+    /// LUT_3D_SIZE 2
+    /// 1 1 1
+    /// 0 1 1
+    /// 1 0 1
+    /// 0 0 1
+    /// 1 1 0
+    /// 0 1 0
+    /// 1 0 0
+    /// 0 0 0
+    /// ";
+    /// let lut = Lut3d::from_cube_file(cube.as_bytes()).unwrap();
+    /// assert_relative_eq!(Colour::solid(0.2, 0.4, 0.6).apply_lut3d(&lut), Colour::solid(0.8, 0.6, 0.4));
+    /// ```
+    pub fn from_cube_file<R: Read>(reader: R) -> Result<Self, LutError> {
+        let mut size = None;
+        let mut domain_min = [0f64; 3];
+        let mut domain_max = [1f64; 3];
+        let mut table = Vec::new();
+        let triple = |values: &[&str], line: usize| -> Result<[f64; 3], LutError> {
+            match values {
+                [r, g, b] => {
+                    let parse = |v: &str| v.parse::<f64>().map_err(|_| LutError::InvalidLine(line));
+                    Ok([parse(r)?, parse(g)?, parse(b)?])
+                }
+                _ => Err(LutError::InvalidLine(line)),
+            }
+        };
+        for (index, line) in BufReader::new(reader).lines().enumerate() {
+            let (line, number) = (line?, index + 1);
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let words: Vec<&str> = line.split_whitespace().collect();
+            match words[0] {
+                "TITLE" => {}
+                "LUT_3D_SIZE" => match words[1..] {
+                    [value] => {
+                        let value = value
+                            .parse::<usize>()
+                            .map_err(|_| LutError::InvalidLine(number))?;
+                        // Checked before reading the entries
+                        entry_count(value)?;
+                        size = Some(value)
+                    }
+                    _ => return Err(LutError::InvalidLine(number)),
+                },
+                "DOMAIN_MIN" => domain_min = triple(&words[1..], number)?,
+                "DOMAIN_MAX" => domain_max = triple(&words[1..], number)?,
+                _ => {
+                    let [r, g, b] = triple(&words, number)?;
+                    table.push(Colour::solid(r, g, b));
+                }
+            }
+        }
+        let size = size.ok_or(LutError::MissingSize)?;
+        Self::new(size, table)?.with_domain(domain_min, domain_max)
+    }
+
+    /// The number of grid points along each axis
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The `size³` entries, red changing fastest
+    pub fn table(&self) -> &[Colour] {
+        &self.table
+    }
+
+    /// The input range each channel is mapped from as `(min, max)`
+    pub fn domain(&self) -> ([f64; 3], [f64; 3]) {
+        (self.domain_min, self.domain_max)
+    }
+
+    /// Gets the entry at grid point `(r, g, b)`
+    pub fn get(&self, r: usize, g: usize, b: usize) -> Colour {
+        self.table[r + self.size * (g + self.size * b)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::{Lut3d, LutError};
+    use crate::Colour;
+
+    #[test]
+    pub fn identity_test() {
+        for size in [2, 3, 17, 33] {
+            let lut = Lut3d::identity(size).unwrap();
+            for i in 0..=10 {
+                let v = i as f64 / 10.0;
+                let colour = Colour::new(v, 1.0 - v, (v * 3.0) % 1.0, v);
+                assert_relative_eq!(colour.apply_lut3d(&lut), colour, epsilon = 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    pub fn inverting_test() {
+        let lut = Lut3d::from_fn(5, |r, g, b| Colour::solid(1.0 - r, 1.0 - g, 1.0 - b)).unwrap();
+        for i in 0..=10 {
+            let v = i as f64 / 10.0;
+            let colour = Colour::new(v, (v * 7.0) % 1.0, 1.0 - v, 0.5);
+            assert_relative_eq!(colour.apply_lut3d(&lut), colour.inverted(), epsilon = 1e-12);
+        }
+        // Out of range inputs are clamped to the domain
+        assert_relative_eq!(
+            Colour::solid(-1.0, 2.0, 0.5).apply_lut3d(&lut),
+            Colour::solid(1.0, 0.0, 0.5)
+        );
+    }
+
+    #[test]
+    pub fn cube_file_test() {
+        let cube = "TITLE \"channel swap\"\n\
+            # swaps red and blue over a [0, 2] domain\n\
+            DOMAIN_MIN 0 0 0\n\
+            DOMAIN_MAX 2 2 2\n\
+            LUT_3D_SIZE 2\n\
+            \n\
+            0 0 0\n0 0 1\n0 1 0\n0 1 1\n\
+            1 0 0\n1 0 1\n1 1 0\n1 1 1\n";
+        let lut = Lut3d::from_cube_file(cube.as_bytes()).unwrap();
+        assert_eq!(lut.size(), 2);
+        assert_eq!(lut.domain(), ([0.0; 3], [2.0; 3]));
+        assert_eq!(lut.get(1, 0, 0), Colour::solid(0.0, 0.0, 1.0));
+        assert_relative_eq!(
+            Colour::solid(0.2, 0.4, 1.2).apply_lut3d(&lut),
+            Colour::solid(0.6, 0.2, 0.1)
+        );
+
+        assert!(matches!(
+            Lut3d::from_cube_file("0 0 0\n".as_bytes()),
+            Err(LutError::MissingSize)
+        ));
+        assert!(matches!(
+            Lut3d::from_cube_file("LUT_3D_SIZE 2\n0 0 0\n".as_bytes()),
+            Err(LutError::InvalidEntryCount {
+                expected: 8,
+                found: 1
+            })
+        ));
+        assert!(matches!(
+            Lut3d::from_cube_file("LUT_3D_SIZE 2\n0 0 zero\n".as_bytes()),
+            Err(LutError::InvalidLine(2))
+        ));
+        assert!(matches!(
+            Lut3d::from_cube_file("LUT_1D_SIZE 2\n".as_bytes()),
+            Err(LutError::InvalidLine(1))
+        ));
+        assert!(matches!(
+            Lut3d::new(1, vec![Colour::grey(0.0)]),
+            Err(LutError::InvalidSize(1))
+        ));
+    }
+
+    #[test]
+    pub fn invalid_size_test() {
+        // An untrusted header can't overflow `size³`
+        for size in [0, 1, 3_000_000, usize::MAX] {
+            assert!(matches!(
+                Lut3d::from_cube_file(format!("LUT_3D_SIZE {size}\n0 0 0\n").as_bytes()),
+                Err(LutError::InvalidSize(s)) if s == size
+            ));
+            assert!(matches!(
+                Lut3d::new(size, vec![]),
+                Err(LutError::InvalidSize(s)) if s == size
+            ));
+        }
+        assert!(matches!(
+            Lut3d::identity(1 << 22),
+            Err(LutError::InvalidSize(_))
+        ));
+    }
+
+    #[test]
+    pub fn invalid_domain_test() {
+        let cube = |min: &str, max: &str| {
+            format!(
+                "DOMAIN_MIN {min}\nDOMAIN_MAX {max}\nLUT_3D_SIZE 2\n{}",
+                "0 0 0\n".repeat(8)
+            )
+        };
+        assert!(Lut3d::from_cube_file(cube("0 0 0", "1 2 3").as_bytes()).is_ok());
+        for (min, max) in [
+            ("0 0 0", "1 0 1"),
+            ("0 1 0", "1 0.5 1"),
+            ("0 0 0", "1 inf 1"),
+            ("NaN 0 0", "1 1 1"),
+        ] {
+            assert!(matches!(
+                Lut3d::from_cube_file(cube(min, max).as_bytes()),
+                Err(LutError::InvalidDomain { .. })
+            ));
+        }
+
+        let lut = Lut3d::identity(2).unwrap();
+        assert!(matches!(
+            lut.clone().with_domain([0.0; 3], [1.0, 1.0, 0.0]),
+            Err(LutError::InvalidDomain {
+                min: [0.0, 0.0, 0.0],
+                max: [1.0, 1.0, 0.0]
+            })
+        ));
+        assert_eq!(
+            lut.with_domain([-1.0; 3], [1.0; 3]).unwrap().domain(),
+            ([-1.0; 3], [1.0; 3])
+        );
+    }
+}
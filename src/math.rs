@@ -0,0 +1,69 @@
+//! The `f64` methods that live in `std` rather than `core`, backed by
+//! `libm` so that the colour maths works under `#![no_std]`.
+//!
+//! When something else in the dependency graph links `std` the inherent
+//! methods come back and take precedence, leaving these unused.
+#![allow(dead_code)]
+
+pub(crate) trait Float {
+    fn abs(self) -> f64;
+    fn atan2(self, other: f64) -> f64;
+    fn cbrt(self) -> f64;
+    fn cos(self) -> f64;
+    fn exp(self) -> f64;
+    fn floor(self) -> f64;
+    fn hypot(self, other: f64) -> f64;
+    fn ln(self) -> f64;
+    fn powf(self, n: f64) -> f64;
+    fn powi(self, n: i32) -> f64;
+    fn rem_euclid(self, rhs: f64) -> f64;
+    fn round(self) -> f64;
+    fn sin(self) -> f64;
+    fn sqrt(self) -> f64;
+}
+
+impl Float for f64 {
+    fn abs(self) -> f64 {
+        libm::fabs(self)
+    }
+    fn atan2(self, other: f64) -> f64 {
+        libm::atan2(self, other)
+    }
+    fn cbrt(self) -> f64 {
+        libm::cbrt(self)
+    }
+    fn cos(self) -> f64 {
+        libm::cos(self)
+    }
+    fn exp(self) -> f64 {
+        libm::exp(self)
+    }
+    fn floor(self) -> f64 {
+        libm::floor(self)
+    }
+    fn hypot(self, other: f64) -> f64 {
+        libm::hypot(self, other)
+    }
+    fn ln(self) -> f64 {
+        libm::log(self)
+    }
+    fn powf(self, n: f64) -> f64 {
+        libm::pow(self, n)
+    }
+    fn powi(self, n: i32) -> f64 {
+        libm::pow(self, n as f64)
+    }
+    fn rem_euclid(self, rhs: f64) -> f64 {
+        let r = libm::fmod(self, rhs);
+        if r < 0f64 { r + libm::fabs(rhs) } else { r }
+    }
+    fn round(self) -> f64 {
+        libm::round(self)
+    }
+    fn sin(self) -> f64 {
+        libm::sin(self)
+    }
+    fn sqrt(self) -> f64 {
+        libm::sqrt(self)
+    }
+}
@@ -0,0 +1,125 @@
+use crate::colour::{BlendSpace, Colour};
+use crate::gradient::Easing;
+
+/// An animation from one colour to another over `duration`, for UI
+/// transitions driven by the elapsed time. Alpha is always
+/// interpolated linearly in the eased `t`.
+///
+/// # Example
+/// ```
+/// use approx::assert_relative_eq;
+/// use tcolour::{BlendSpace, Colour, ColourTween, Easing};
+///
+/// let fade = ColourTween::new(Colour::grey(0.0), Colour::grey(1.0), 2.0)
+///     .with_easing(Easing::SmoothStep)
+///     .with_space(BlendSpace::Srgb);
+///
+/// assert_relative_eq!(fade.at(1.0), Colour::grey(0.5));
+/// assert!(!fade.is_done(1.0));
+/// assert!(fade.is_done(2.0));
+/// ```
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ColourTween {
+    pub from: Colour,
+    pub to: Colour,
+    /// In whatever unit `elapsed` is given in
+    pub duration: f64,
+    pub easing: Easing,
+    pub space: BlendSpace,
+}
+
+impl ColourTween {
+    /// Creates a linear tween in sRGB
+    pub fn new(from: Colour, to: Colour, duration: f64) -> Self {
+        Self {
+            from,
+            to,
+            duration,
+            easing: Easing::Linear,
+            space: BlendSpace::Srgb,
+        }
+    }
+
+    /// Sets the easing applied to the progress of the tween
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Sets the space the colours are interpolated in, `BlendSpace::Linear`
+    /// mixes them as light
+    pub fn with_space(mut self, space: BlendSpace) -> Self {
+        self.space = space;
+        self
+    }
+
+    /// Gets the colour `elapsed` into the tween, `from` before it has
+    /// started and `to` once it is done. A tween without a positive
+    /// duration is always done.
+    pub fn at(&self, elapsed: f64) -> Colour {
+        let progress = if self.duration > 0f64 {
+            (elapsed / self.duration).clamp(0f64, 1f64)
+        } else {
+            1f64
+        };
+        let t = self.easing.apply(progress);
+        let alpha = self.from.a + (self.to.a - self.from.a) * t;
+        match self.space {
+            BlendSpace::Srgb => self.from.lerp(self.to, t),
+            BlendSpace::Linear => self.from.to_linear().lerp(self.to.to_linear(), t).to_srgb(),
+        }
+        .with_alpha(alpha)
+    }
+
+    /// Whether the tween has reached `to` by `elapsed`, which a tween
+    /// without a positive duration always has
+    pub fn is_done(&self, elapsed: f64) -> bool {
+        self.duration <= 0f64 || elapsed >= self.duration
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+    use strum::IntoEnumIterator;
+
+    use super::ColourTween;
+    use crate::{BlendSpace, Colour, Easing};
+
+    #[test]
+    pub fn tween_test() {
+        let (from, to) = (
+            Colour::new(0.8, 0.4, 0.2, 0.0),
+            Colour::new(0.2, 0.6, 1.0, 1.0),
+        );
+        for easing in Easing::iter() {
+            for space in BlendSpace::iter() {
+                let tween = ColourTween::new(from, to, 1.5)
+                    .with_easing(easing)
+                    .with_space(space);
+                assert_relative_eq!(tween.at(0.0), from, epsilon = 1e-12);
+                assert_relative_eq!(tween.at(1.5), to, epsilon = 1e-12);
+                // Held at either end outside of the duration
+                assert_relative_eq!(tween.at(-1.0), from, epsilon = 1e-12);
+                assert_relative_eq!(tween.at(10.0), to, epsilon = 1e-12);
+                assert!(!tween.is_done(1.0));
+                assert!(tween.is_done(1.5));
+            }
+        }
+
+        let (black, white) = (Colour::grey(0.0), Colour::grey(1.0));
+        let tween = ColourTween::new(black, white.with_alpha(0.0), 2.0);
+        assert_relative_eq!(tween.at(0.5), Colour::grey(0.25).with_alpha(0.75));
+        let eased = tween.with_easing(Easing::EaseIn);
+        assert_relative_eq!(eased.at(1.0), Colour::grey(0.25).with_alpha(0.75));
+        // Half the light of white is brighter than 0.5 once encoded
+        let linear = ColourTween::new(black, white, 2.0).with_space(BlendSpace::Linear);
+        assert_relative_eq!(linear.at(1.0), Colour::grey(0.5).to_srgb());
+
+        let instant = ColourTween::new(black, white, 0.0);
+        assert_eq!(instant.at(0.0), white);
+        assert!(instant.is_done(0.0));
+        assert_eq!(instant.at(-1.0), white);
+        assert!(instant.is_done(-1.0));
+    }
+}